@@ -1,7 +1,8 @@
+use alloy_primitives::Address;
 use alloy_sol_types::sol;
 
 sol! {
-    #[derive(Debug, Default, PartialEq, Eq)]
+    #[derive(Debug, Default, PartialEq, Eq, Hash)]
     struct PoolKey {
         address currency0;
         address currency1;
@@ -49,6 +50,17 @@ sol! {
         bytes hookData;
     }
 
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct MintPositionFromDeltasParams {
+        PoolKey poolKey;
+        int24 tickLower;
+        int24 tickUpper;
+        uint128 amount0Max;
+        uint128 amount1Max;
+        address owner;
+        bytes hookData;
+    }
+
     #[derive(Debug, Default, PartialEq, Eq)]
     struct BurnPositionParams {
         uint256 tokenId;
@@ -213,4 +225,21 @@ sol! {
             external
             payable;
     }
+
+    interface IPermit2Forwarder {
+        function permit(address owner, IAllowanceTransfer.PermitSingle calldata permitSingle, bytes calldata signature)
+            external
+            payable
+            returns (bytes memory err);
+    }
+}
+
+impl PoolKey {
+    /// Returns whether the pool pairs the native currency, which always sorts first as
+    /// [`Address::ZERO`]
+    #[inline]
+    #[must_use]
+    pub fn is_native(&self) -> bool {
+        self.currency0 == Address::ZERO
+    }
 }
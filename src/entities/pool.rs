@@ -1,11 +1,49 @@
 use crate::prelude::{Error, *};
-use alloy_primitives::{aliases::U24, keccak256, uint, Address, ChainId, B256, I256, U160};
+use alloc::sync::Arc;
+use alloy_primitives::{
+    aliases::{I24, U24},
+    keccak256, uint, Address, ChainId, B256, I256, U160,
+};
 use alloy_sol_types::SolValue;
 use uniswap_sdk_core::prelude::*;
 use uniswap_v3_sdk::prelude::*;
 
 pub const DYANMIC_FEE_FLAG: U24 = uint!(0x800000_U24);
 
+/// The minimum tick spacing of a V4 pool, i.e. `TickMath.MIN_TICK_SPACING`
+pub const MIN_TICK_SPACING: i32 = 1;
+/// The maximum tick spacing of a V4 pool, i.e. `TickMath.MAX_TICK_SPACING`
+pub const MAX_TICK_SPACING: i32 = i16::MAX as i32;
+
+/// Returns the maximum liquidity that can reference a single tick of a pool with the given tick
+/// spacing, i.e. `Pool.tickSpacingToMaxLiquidityPerTick`
+///
+/// Unlike V3's `Tick.tickSpacingToMaxLiquidityPerTick`, the minimum tick is rounded down rather
+/// than toward zero, so the tick count is one higher whenever `MIN_TICK` is not a multiple of the
+/// tick spacing.
+///
+/// ## Arguments
+///
+/// * `tick_spacing`: The tick spacing of the pool
+#[inline]
+#[must_use]
+pub const fn max_liquidity_per_tick(tick_spacing: i32) -> u128 {
+    let min_tick = MIN_TICK_I32.div_euclid(tick_spacing);
+    let max_tick = MAX_TICK_I32 / tick_spacing;
+    let num_ticks = (max_tick - min_tick) as u128 + 1;
+    u128::MAX / num_ticks
+}
+
+/// Converts a tick index to an `i32`, saturating an index that does not fit, so that it can be
+/// range checked without narrowing it to an `int24`, which panics when out of range
+pub(crate) fn tick_index_to_i32<I: TickIndex>(index: I) -> i32 {
+    TryInto::<i32>::try_into(index).unwrap_or(if index < I::from_i24(I24::ZERO) {
+        i32::MIN
+    } else {
+        i32::MAX
+    })
+}
+
 /// Represents a V4 pool
 #[derive(Clone, Debug)]
 pub struct Pool<TP = NoTickDataProvider>
@@ -76,6 +114,53 @@ impl Pool {
         })
     }
 
+    /// Returns the pool key like [`Self::get_pool_key`], after checking that the hook address is
+    /// valid for the fee with [`is_valid_hook_address`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `currency_a`: One of the currencies in the pool
+    /// * `currency_b`: The other currency in the pool
+    /// * `fee`: The fee in hundredths of a bips of the input amount of every swap
+    /// * `tick_spacing`: The tickSpacing of the pool
+    /// * `hooks`: The address of the hook contract
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidHookAddress`] if the pool manager would reject the hook address,
+    /// e.g. a returns-delta flag without its action flag.
+    #[inline]
+    pub fn get_pool_key_validated<I: TickIndex>(
+        currency_a: &Currency,
+        currency_b: &Currency,
+        fee: U24,
+        tick_spacing: I,
+        hooks: Address,
+    ) -> Result<PoolKey, Error> {
+        if !is_valid_hook_address(hooks, fee) {
+            return Err(Error::InvalidHookAddress(hooks));
+        }
+        Self::get_pool_key(currency_a, currency_b, fee, tick_spacing, hooks)
+    }
+
+    /// Computes the id of the pool with the given parameters
+    ///
+    /// Use this for untrusted input, e.g. currencies that have not been validated as a pool pair.
+    /// For the parameters of an already constructed [`Pool`], [`Self::pool_id_unchecked`] cannot
+    /// fail and avoids the error handling.
+    ///
+    /// ## Arguments
+    ///
+    /// * `currency_a`: One of the currencies in the pool
+    /// * `currency_b`: The other currency in the pool
+    /// * `fee`: The fee in hundredths of a bips of the input amount of every swap
+    /// * `tick_spacing`: The tickSpacing of the pool
+    /// * `hooks`: The address of the hook contract
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::Core`] if the currencies cannot be sorted, i.e. they are the same token or
+    /// on different chains.
     #[inline]
     pub fn get_pool_id<I: TickIndex>(
         currency_a: &Currency,
@@ -97,6 +182,57 @@ impl Pool {
         ))
     }
 
+    /// Computes the id of the pool with the given parameters like [`Self::get_pool_id`], without
+    /// validating the currencies
+    ///
+    /// The currencies are sorted by address with the native currency first. Only use this for
+    /// parameters already validated as a pool pair, such as those of a constructed [`Pool`];
+    /// identical currencies or currencies on different chains yield the id of a pool that cannot
+    /// exist.
+    ///
+    /// ## Arguments
+    ///
+    /// * `currency_a`: One of the currencies in the pool
+    /// * `currency_b`: The other currency in the pool
+    /// * `fee`: The fee in hundredths of a bips of the input amount of every swap
+    /// * `tick_spacing`: The tickSpacing of the pool
+    /// * `hooks`: The address of the hook contract
+    #[inline]
+    #[must_use]
+    pub fn pool_id_unchecked<I: TickIndex>(
+        currency_a: &Currency,
+        currency_b: &Currency,
+        fee: U24,
+        tick_spacing: I,
+        hooks: Address,
+    ) -> B256 {
+        let address_a = if currency_a.is_native() {
+            Address::ZERO
+        } else {
+            currency_a.address()
+        };
+        let address_b = if currency_b.is_native() {
+            Address::ZERO
+        } else {
+            currency_b.address()
+        };
+        let (currency0_addr, currency1_addr) = if address_a < address_b {
+            (address_a, address_b)
+        } else {
+            (address_b, address_a)
+        };
+        keccak256(
+            (
+                currency0_addr,
+                currency1_addr,
+                fee,
+                tick_spacing.to_i24(),
+                hooks,
+            )
+                .abi_encode(),
+        )
+    }
+
     /// Constructs a pool
     ///
     /// ## Arguments
@@ -109,6 +245,11 @@ impl Pool {
     /// * `hooks`: The address of the hook contract
     /// * `sqrt_price_x96`: The sqrt of the current ratio of amounts of currency1 to currency0
     /// * `liquidity`: The current value of in range liquidity
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidTickSpacing`] if `tick_spacing` is not within
+    /// [`MIN_TICK_SPACING`] and [`MAX_TICK_SPACING`].
     #[inline]
     pub fn new(
         currency_a: Currency,
@@ -130,6 +271,102 @@ impl Pool {
             NoTickDataProvider,
         )
     }
+
+    /// Constructs a pool priced at the given tick
+    ///
+    /// ## Arguments
+    ///
+    /// * `currency_a`: One of the currencies in the pool
+    /// * `currency_b`: The other currency in the pool
+    /// * `fee`: The fee in hundredths of a bips of the input amount of every swap that is collected
+    ///   by the pool
+    /// * `tick_spacing`: The tickSpacing of the pool
+    /// * `hooks`: The address of the hook contract
+    /// * `tick`: The current tick of the pool, from which the sqrt price is computed
+    /// * `liquidity`: The current value of in range liquidity
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::TickOutOfRange`] if `tick` is not within [`MIN_TICK`] and [`MAX_TICK`],
+    /// an error if `tick` is [`MAX_TICK`], whose sqrt price is exclusive, or
+    /// [`Error::InvalidTickSpacing`] if `tick_spacing` is not within [`MIN_TICK_SPACING`] and
+    /// [`MAX_TICK_SPACING`].
+    #[inline]
+    pub fn new_at_tick(
+        currency_a: Currency,
+        currency_b: Currency,
+        fee: U24,
+        tick_spacing: <NoTickDataProvider as TickDataProvider>::Index,
+        hooks: Address,
+        tick: i32,
+        liquidity: u128,
+    ) -> Result<Self, Error> {
+        if !(MIN_TICK_I32..=MAX_TICK_I32).contains(&tick) {
+            return Err(Error::TickOutOfRange { tick });
+        }
+        Self::new(
+            currency_a,
+            currency_b,
+            fee,
+            tick_spacing,
+            hooks,
+            get_sqrt_ratio_at_tick(tick.to_i24())?,
+            liquidity,
+        )
+    }
+
+    /// Constructs a pool, treating the canonical WETH9 token of the chain as the native currency
+    ///
+    /// ## Note
+    ///
+    /// In V4, native ETH and WETH are distinct currencies: a native pool is keyed by
+    /// [`Address::ZERO`], while a WETH pool is keyed by the WETH9 address, and the two have
+    /// different pool ids. [`Pool::new`] always keys a WETH currency by its token address. Use
+    /// this constructor when a WETH currency is meant to represent native ETH.
+    ///
+    /// ## Arguments
+    ///
+    /// * `currency_a`: One of the currencies in the pool
+    /// * `currency_b`: The other currency in the pool
+    /// * `fee`: The fee in hundredths of a bips of the input amount of every swap that is collected
+    ///   by the pool
+    /// * `tick_spacing`: The tickSpacing of the pool
+    /// * `hooks`: The address of the hook contract
+    /// * `sqrt_price_x96`: The sqrt of the current ratio of amounts of currency1 to currency0
+    /// * `liquidity`: The current value of in range liquidity
+    #[inline]
+    pub fn new_native(
+        currency_a: Currency,
+        currency_b: Currency,
+        fee: U24,
+        tick_spacing: <NoTickDataProvider as TickDataProvider>::Index,
+        hooks: Address,
+        sqrt_price_x96: U160,
+        liquidity: u128,
+    ) -> Result<Self, Error> {
+        Self::new(
+            Self::wrapped_native_to_native(currency_a),
+            Self::wrapped_native_to_native(currency_b),
+            fee,
+            tick_spacing,
+            hooks,
+            sqrt_price_x96,
+            liquidity,
+        )
+    }
+
+    /// Maps the canonical WETH9 token of the currency's chain to the native currency, leaving any
+    /// other currency untouched
+    fn wrapped_native_to_native(currency: Currency) -> Currency {
+        match currency {
+            Currency::Token(token)
+                if WETH9::on_chain(token.chain_id()).is_some_and(|weth| weth.equals(&token)) =>
+            {
+                Currency::NativeCurrency(Ether::on_chain(token.chain_id()))
+            }
+            currency => currency,
+        }
+    }
 }
 
 impl<TP: TickDataProvider> Pool<TP> {
@@ -146,6 +383,11 @@ impl<TP: TickDataProvider> Pool<TP> {
     /// * `sqrt_price_x96`: The sqrt of the current ratio of amounts of currency1 to currency0
     /// * `liquidity`: The current value of in range liquidity
     /// * `tick_data_provider`: A tick data provider that can return tick data
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidTickSpacing`] if `tick_spacing` is not within
+    /// [`MIN_TICK_SPACING`] and [`MAX_TICK_SPACING`].
     #[inline]
     #[allow(clippy::too_many_arguments)]
     pub fn new_with_tick_data_provider(
@@ -162,6 +404,12 @@ impl<TP: TickDataProvider> Pool<TP> {
         if fee == DYANMIC_FEE_FLAG {
             assert_ne!(hooks, Address::ZERO, "Dynamic fee pool requires a hook");
         }
+        let tick_spacing_i32 = tick_index_to_i32(tick_spacing);
+        if !(MIN_TICK_SPACING..=MAX_TICK_SPACING).contains(&tick_spacing_i32) {
+            return Err(Error::InvalidTickSpacing {
+                tick_spacing: tick_spacing_i32,
+            });
+        }
         let pool_key =
             Pool::get_pool_key(&currency_a, &currency_b, fee, tick_spacing.to_i24(), hooks)?;
         let pool_id = Pool::get_pool_id(&currency_a, &currency_b, fee, tick_spacing, hooks)?;
@@ -186,6 +434,12 @@ impl<TP: TickDataProvider> Pool<TP> {
         })
     }
 
+    /// Returns the tick spacing of the pool as the `int24` used in the pool key
+    #[inline]
+    pub fn tick_spacing_i24(&self) -> I24 {
+        self.tick_spacing.to_i24()
+    }
+
     #[inline]
     pub const fn token0(&self) -> &Currency {
         &self.currency0
@@ -313,6 +567,28 @@ impl<TP: TickDataProvider> Pool<TP> {
 }
 
 impl<TP: Clone + TickDataProvider> Pool<TP> {
+    /// Returns a copy of the pool whose tick data provider is shared behind an [`Arc`](alloc::sync::Arc)
+    ///
+    /// Clones of the returned pool, including the post-swap pools returned by
+    /// [`Self::get_output_amount`] and [`Self::get_input_amount`], share a single copy of the tick
+    /// data instead of cloning it.
+    #[inline]
+    pub fn share_tick_data(&self) -> Pool<SharedTickDataProvider<TP>> {
+        Pool {
+            currency0: self.currency0.clone(),
+            currency1: self.currency1.clone(),
+            fee: self.fee,
+            tick_spacing: self.tick_spacing,
+            sqrt_price_x96: self.sqrt_price_x96,
+            hooks: self.hooks,
+            liquidity: self.liquidity,
+            tick_current: self.tick_current,
+            tick_data_provider: SharedTickDataProvider::new(self.tick_data_provider.clone()),
+            pool_key: self.pool_key.clone(),
+            pool_id: self.pool_id,
+        }
+    }
+
     /// Given an input amount of a token, return the computed output amount, and a pool with state
     /// updated after the trade
     ///
@@ -557,6 +833,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn dynamic_fee_pool_is_keyed_by_the_dynamic_fee_flag() {
+        let hooks = address!("fff0000000000000000000000000000000000000");
+        let pool = Pool::new(
+            Currency::Token(USDC.clone()),
+            Currency::Token(WETH.clone()),
+            DYANMIC_FEE_FLAG,
+            10,
+            hooks,
+            encode_sqrt_ratio_x96(1, 1),
+            0,
+        )
+        .unwrap();
+        assert_eq!(pool.pool_key.fee, DYANMIC_FEE_FLAG);
+        // keccak256(abi.encode(USDC, WETH, 0x800000, 10, hooks))
+        assert_eq!(
+            pool.pool_id,
+            b256!("daa335053276e01877a1e2fde4f760ef901f71f3d223b45187553a785cafa8a7")
+        );
+        assert_eq!(
+            Pool::get_pool_id(
+                &WETH.clone().into(),
+                &USDC.clone().into(),
+                DYANMIC_FEE_FLAG,
+                10,
+                hooks,
+            )
+            .unwrap(),
+            pool.pool_id
+        );
+    }
+
     #[test]
     fn get_pool_id_returns_correct_pool_id() {
         let result1 = Pool::get_pool_id(
@@ -583,6 +891,148 @@ mod tests {
         assert_eq!(result2, result1);
     }
 
+    #[test]
+    fn get_pool_id_matches_solidity_to_id() {
+        let hooks = address!("0000000000000000000000000000000000000fc0");
+        let eth: Currency = ETHER.clone().into();
+        let weth: Currency = WETH.clone().into();
+        let usdc: Currency = USDC.clone().into();
+        let dai: Currency = DAI.clone().into();
+        let cases = [
+            (
+                &eth,
+                &usdc,
+                FeeAmount::LOW.into(),
+                10,
+                Address::ZERO,
+                b256!("21c67e77068de97969ba93d4aab21826d33ca12bb9f565d8496e8fda8a82ca27"),
+            ),
+            (
+                &usdc,
+                &eth,
+                FeeAmount::MEDIUM.into(),
+                60,
+                Address::ZERO,
+                b256!("dce6394339af00981949f5f3baf27e3610c76326a700af57e4b3e3ae4977f78d"),
+            ),
+            (
+                &eth,
+                &dai,
+                FeeAmount::HIGH.into(),
+                200,
+                Address::ZERO,
+                b256!("f30bb08535b65d93477b210f2bf9fbffc9d02d98209c4f6e61d035a6d25769b6"),
+            ),
+            (
+                &weth,
+                &usdc,
+                FeeAmount::MEDIUM.into(),
+                60,
+                Address::ZERO,
+                b256!("b9fd8b8b6580b80432037a379071b6fb22bbceff6bafbd57390889c01d8757b9"),
+            ),
+            (
+                &usdc,
+                &dai,
+                FeeAmount::LOWEST.into(),
+                1,
+                Address::ZERO,
+                b256!("d967702f17f83d907b36e66c9a62eb50ac327432c581d5b273a76519692434be"),
+            ),
+            (
+                &dai,
+                &usdc,
+                FeeAmount::LOW.into(),
+                10,
+                Address::ZERO,
+                b256!("8bce1ae39d5f8fba09c172d539e290ee96f18ad917a044f7ed7a490348f88bcc"),
+            ),
+            (
+                &eth,
+                &usdc,
+                FeeAmount::MEDIUM.into(),
+                60,
+                hooks,
+                b256!("46fd3e465c5c8cde86c5053ab87b35ac5be457097a8614055f5a4ad9d33d1ab5"),
+            ),
+            (
+                &weth,
+                &dai,
+                DYANMIC_FEE_FLAG,
+                60,
+                hooks,
+                b256!("c472df85234be177ddbfe8cdfd1aa42ab2ffd9c2d1700087a3a864e54f236e6a"),
+            ),
+        ];
+        for (currency_a, currency_b, fee, tick_spacing, hooks, expected) in cases {
+            assert_eq!(
+                Pool::get_pool_id(currency_a, currency_b, fee, tick_spacing, hooks).unwrap(),
+                expected
+            );
+            assert_eq!(
+                Pool::get_pool_id(currency_b, currency_a, fee, tick_spacing, hooks).unwrap(),
+                expected
+            );
+            assert_eq!(
+                Pool::pool_id_unchecked(currency_a, currency_b, fee, tick_spacing, hooks),
+                expected
+            );
+            assert_eq!(
+                Pool::pool_id_unchecked(currency_b, currency_a, fee, tick_spacing, hooks),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn pool_id_unchecked_agrees_with_get_pool_id() {
+        let pool = &*USDC_DAI;
+        let unchecked = Pool::pool_id_unchecked(
+            &pool.currency1,
+            &pool.currency0,
+            pool.fee,
+            pool.tick_spacing,
+            pool.hooks,
+        );
+        let checked = Pool::get_pool_id(
+            &pool.currency1,
+            &pool.currency0,
+            pool.fee,
+            pool.tick_spacing,
+            pool.hooks,
+        )
+        .unwrap();
+        assert_eq!(unchecked, checked);
+        assert_eq!(unchecked, pool.pool_id);
+    }
+
+    #[test]
+    fn get_pool_key_validated_rejects_malformed_hook_permissions() {
+        // only the beforeSwapReturnsDelta flag, without beforeSwap
+        let hooks = address!("0000000000000000000000000000000000000008");
+        assert!(matches!(
+            Pool::get_pool_key_validated(
+                &USDC.clone().into(),
+                &DAI.clone().into(),
+                FeeAmount::LOWEST.into(),
+                10,
+                hooks,
+            ),
+            Err(Error::InvalidHookAddress(address)) if address == hooks
+        ));
+        assert_eq!(
+            Pool::get_pool_key_validated(
+                &USDC.clone().into(),
+                &DAI.clone().into(),
+                FeeAmount::LOWEST.into(),
+                10,
+                Address::ZERO,
+            )
+            .unwrap(),
+            USDC_DAI.pool_key
+        );
+    }
+
     #[test]
     fn get_pool_key_returns_correct_pool_key() {
         let result1 = Pool::get_pool_key(
@@ -615,6 +1065,155 @@ mod tests {
         assert_eq!(result2, result1);
     }
 
+    #[test]
+    fn get_pool_key_keys_native_currency_as_zero_address() {
+        let native_key = Pool::get_pool_key(
+            &USDC.clone().into(),
+            &ETHER.clone().into(),
+            FeeAmount::LOW.into(),
+            10,
+            Address::ZERO,
+        )
+        .unwrap();
+        assert_eq!(native_key.currency0, Address::ZERO);
+        assert_eq!(native_key.currency1, USDC.address);
+        assert!(native_key.is_native());
+
+        let token_key = Pool::get_pool_key(
+            &USDC.clone().into(),
+            &WETH.clone().into(),
+            FeeAmount::LOW.into(),
+            10,
+            Address::ZERO,
+        )
+        .unwrap();
+        assert!(!token_key.is_native());
+        assert!(!USDC_DAI.pool_key.is_native());
+    }
+
+    mod new_at_tick {
+        use super::*;
+
+        fn pool_at_tick(tick: i32) -> Result<Pool, Error> {
+            Pool::new_at_tick(
+                USDC.clone().into(),
+                DAI.clone().into(),
+                FeeAmount::MEDIUM.into(),
+                60,
+                Address::ZERO,
+                tick,
+                0,
+            )
+        }
+
+        #[test]
+        fn sets_the_current_tick() {
+            for tick in [0, 60, -120, 12345, -54321, MIN_TICK_I32, MAX_TICK_I32 - 1] {
+                let pool = pool_at_tick(tick).unwrap();
+                assert_eq!(pool.tick_current, tick);
+                assert_eq!(
+                    pool.sqrt_price_x96,
+                    get_sqrt_ratio_at_tick(tick.to_i24()).unwrap()
+                );
+            }
+        }
+
+        #[test]
+        fn errors_on_tick_out_of_range() {
+            for tick in [
+                MAX_TICK_I32 + 1,
+                MIN_TICK_I32 - 1,
+                1 << 23,
+                -(1 << 23) - 1,
+                i32::MAX,
+                i32::MIN,
+            ] {
+                assert!(matches!(
+                    pool_at_tick(tick),
+                    Err(Error::TickOutOfRange { tick: t }) if t == tick
+                ));
+            }
+        }
+
+        #[test]
+        fn errors_at_max_tick() {
+            assert!(pool_at_tick(MAX_TICK_I32).is_err());
+        }
+    }
+
+    mod new_native {
+        use super::*;
+
+        #[test]
+        fn treats_weth_as_native() {
+            let pool = Pool::new_native(
+                Currency::Token(WETH.clone()),
+                Currency::Token(USDC.clone()),
+                FeeAmount::MEDIUM.into(),
+                60,
+                Address::ZERO,
+                encode_sqrt_ratio_x96(1, 1),
+                0,
+            )
+            .unwrap();
+            assert!(pool.currency0.is_native());
+            assert_eq!(pool.pool_key.currency0, Address::ZERO);
+            assert_eq!(pool.pool_key.currency1, USDC.address());
+        }
+
+        #[test]
+        fn matches_native_pool_and_differs_from_weth_pool() {
+            let native_pool = Pool::new_native(
+                Currency::Token(USDC.clone()),
+                Currency::Token(WETH.clone()),
+                FeeAmount::MEDIUM.into(),
+                60,
+                Address::ZERO,
+                encode_sqrt_ratio_x96(1, 1),
+                0,
+            )
+            .unwrap();
+            let ether_pool = Pool::new(
+                Currency::NativeCurrency(ETHER.clone()),
+                Currency::Token(USDC.clone()),
+                FeeAmount::MEDIUM.into(),
+                60,
+                Address::ZERO,
+                encode_sqrt_ratio_x96(1, 1),
+                0,
+            )
+            .unwrap();
+            let weth_pool = Pool::new(
+                Currency::Token(WETH.clone()),
+                Currency::Token(USDC.clone()),
+                FeeAmount::MEDIUM.into(),
+                60,
+                Address::ZERO,
+                encode_sqrt_ratio_x96(1, 1),
+                0,
+            )
+            .unwrap();
+            assert_eq!(native_pool.pool_id, ether_pool.pool_id);
+            assert_ne!(native_pool.pool_id, weth_pool.pool_id);
+            assert_ne!(native_pool.pool_key, weth_pool.pool_key);
+        }
+
+        #[test]
+        fn leaves_other_tokens_untouched() {
+            let pool = Pool::new_native(
+                Currency::Token(USDC.clone()),
+                Currency::Token(DAI.clone()),
+                FeeAmount::LOWEST.into(),
+                10,
+                Address::ZERO,
+                encode_sqrt_ratio_x96(1, 1),
+                0,
+            )
+            .unwrap();
+            assert_eq!(pool.pool_id, USDC_DAI.pool_id);
+        }
+    }
+
     #[test]
     fn currency0_always_is_the_currency_that_sorts_before() {
         assert_eq!(USDC_DAI.currency0, DAI.clone().into());
@@ -743,6 +1342,133 @@ mod tests {
         }
     }
 
+    mod tick_spacing_i24 {
+        use super::*;
+
+        fn pool_with_tick_spacing(tick_spacing: i32) -> Pool {
+            Pool::new(
+                USDC.clone().into(),
+                DAI.clone().into(),
+                FeeAmount::LOWEST.into(),
+                tick_spacing,
+                Address::ZERO,
+                encode_sqrt_ratio_x96(1, 1),
+                0,
+            )
+            .unwrap()
+        }
+
+        #[test]
+        fn matches_pool_key() {
+            assert_eq!(USDC_DAI.tick_spacing_i24().as_i32(), 10);
+            assert_eq!(USDC_DAI.tick_spacing_i24(), USDC_DAI.pool_key.tickSpacing);
+        }
+
+        #[test]
+        fn does_not_truncate_max_tick_spacing() {
+            let pool = pool_with_tick_spacing(32767);
+            assert_eq!(pool.tick_spacing_i24().as_i32(), 32767);
+            assert_eq!(pool.tick_spacing_i24(), pool.pool_key.tickSpacing);
+        }
+    }
+
+    #[test]
+    fn max_liquidity_per_tick_matches_pool_library() {
+        // 1774545 ticks
+        assert_eq!(
+            max_liquidity_per_tick(1),
+            191_757_530_477_355_301_479_181_766_273_477
+        );
+        // 177456 ticks
+        assert_eq!(
+            max_liquidity_per_tick(10),
+            1_917_559_095_893_846_719_543_856_547_154_045
+        );
+        // 29576 ticks
+        assert_eq!(
+            max_liquidity_per_tick(60),
+            11_505_354_575_363_080_317_263_139_282_924_270
+        );
+        // 8874 ticks
+        assert_eq!(
+            max_liquidity_per_tick(200),
+            38_345_995_821_606_768_476_828_330_790_147_420
+        );
+        // 772 ticks
+        assert_eq!(
+            max_liquidity_per_tick(2302),
+            440_780_268_032_303_709_149_448_973_357_212_709
+        );
+        // 3 ticks: min, 0 and max
+        assert_eq!(
+            max_liquidity_per_tick(887272),
+            113_427_455_640_312_821_154_458_202_477_256_070_485
+        );
+    }
+
+    mod invalid_tick_spacing {
+        use super::*;
+
+        fn new_pool(tick_spacing: i32) -> Result<Pool, Error> {
+            Pool::new(
+                USDC.clone().into(),
+                DAI.clone().into(),
+                FeeAmount::LOWEST.into(),
+                tick_spacing,
+                Address::ZERO,
+                encode_sqrt_ratio_x96(1, 1),
+                0,
+            )
+        }
+
+        #[test]
+        fn rejects_zero() {
+            assert!(matches!(
+                new_pool(0),
+                Err(Error::InvalidTickSpacing { tick_spacing: 0 })
+            ));
+        }
+
+        #[test]
+        fn rejects_negative() {
+            assert!(matches!(
+                new_pool(-60),
+                Err(Error::InvalidTickSpacing { tick_spacing: -60 })
+            ));
+        }
+
+        #[test]
+        fn rejects_above_max() {
+            assert!(matches!(
+                new_pool(MAX_TICK_SPACING + 1),
+                Err(Error::InvalidTickSpacing {
+                    tick_spacing: 32768
+                })
+            ));
+            assert!(matches!(
+                new_pool(I24::MAX.as_i32()),
+                Err(Error::InvalidTickSpacing { .. })
+            ));
+        }
+
+        #[test]
+        fn rejects_outside_int24() {
+            for tick_spacing in [I24::MAX.as_i32() + 1, 1 << 24, i32::MAX, i32::MIN] {
+                assert!(matches!(
+                    new_pool(tick_spacing),
+                    Err(Error::InvalidTickSpacing { tick_spacing: t }) if t == tick_spacing
+                ));
+            }
+        }
+
+        #[test]
+        fn accepts_valid() {
+            assert_eq!(new_pool(60).unwrap().tick_spacing, 60);
+            assert_eq!(new_pool(MIN_TICK_SPACING).unwrap().tick_spacing, 1);
+            assert_eq!(new_pool(MAX_TICK_SPACING).unwrap().tick_spacing, 32767);
+        }
+    }
+
     #[test]
     fn chain_id_returns_chain_id_of_currencies() {
         assert_eq!(USDC_DAI.chain_id(), 1);
@@ -758,6 +1484,7 @@ mod tests {
 
     mod swaps {
         use super::*;
+        use alloc::sync::Arc;
         use once_cell::sync::Lazy;
 
         static POOL: Lazy<Pool<Vec<Tick>>> = Lazy::new(|| {
@@ -792,6 +1519,67 @@ mod tests {
                 assert!(output_amount.currency.equals(&USDC.clone()));
                 assert_eq!(output_amount.quotient(), 98.into());
             }
+
+            #[test]
+            fn token_and_currency_amounts_are_equivalent() {
+                let token_amount = CurrencyAmount::from_raw_amount(USDC.clone(), 100).unwrap();
+                let currency_amount =
+                    CurrencyAmount::from_raw_amount(Currency::Token(USDC.clone()), 100).unwrap();
+                let (from_token, pool_from_token) =
+                    POOL.get_output_amount(&token_amount, None).unwrap();
+                let (from_currency, pool_from_currency) =
+                    POOL.get_output_amount(&currency_amount, None).unwrap();
+                assert!(from_token.currency.equals(&from_currency.currency));
+                assert_eq!(from_token.quotient(), from_currency.quotient());
+                assert_eq!(pool_from_token, pool_from_currency);
+            }
+        }
+
+        #[test]
+        fn post_swap_pool_retains_tick_data_provider() {
+            let amount = CurrencyAmount::from_raw_amount(USDC.clone(), 100).unwrap();
+            let (first_output, pool_after) = POOL.get_output_amount(&amount, None).unwrap();
+            assert_eq!(pool_after.tick_data_provider, POOL.tick_data_provider);
+            assert_ne!(pool_after.sqrt_price_x96, POOL.sqrt_price_x96);
+
+            // the post-swap pool can keep swapping through its ticks in either direction
+            let (second_output, pool_after) = pool_after.get_output_amount(&amount, None).unwrap();
+            assert!(second_output.quotient() <= first_output.quotient());
+            let (_, pool_after) = pool_after.get_output_amount(&second_output, None).unwrap();
+            assert_eq!(pool_after.tick_data_provider, POOL.tick_data_provider);
+        }
+
+        #[test]
+        fn shared_tick_data_is_not_deep_cloned() {
+            let shared = POOL.share_tick_data();
+            let usdc_to_dai = shared.clone();
+            let dai_to_usdc = shared.clone();
+            assert!(Arc::ptr_eq(
+                &usdc_to_dai.tick_data_provider.0,
+                &dai_to_usdc.tick_data_provider.0
+            ));
+
+            let usdc_in = CurrencyAmount::from_raw_amount(USDC.clone(), 100).unwrap();
+            let (dai_out, usdc_to_dai_after) =
+                usdc_to_dai.get_output_amount(&usdc_in, None).unwrap();
+            let dai_in = CurrencyAmount::from_raw_amount(DAI.clone(), 100).unwrap();
+            let (usdc_out, dai_to_usdc_after) =
+                dai_to_usdc.get_output_amount(&dai_in, None).unwrap();
+            assert!(dai_out.currency.equals(&DAI.clone()));
+            assert_eq!(dai_out.quotient(), 98.into());
+            assert!(usdc_out.currency.equals(&USDC.clone()));
+            assert_eq!(usdc_out.quotient(), 98.into());
+
+            // the post-swap pools still point at the one shared tick map
+            assert!(Arc::ptr_eq(
+                &usdc_to_dai_after.tick_data_provider.0,
+                &shared.tick_data_provider.0
+            ));
+            assert!(Arc::ptr_eq(
+                &dai_to_usdc_after.tick_data_provider.0,
+                &shared.tick_data_provider.0
+            ));
+            assert_eq!(Arc::strong_count(&shared.tick_data_provider.0), 5);
         }
 
         mod get_input_amount {
@@ -812,6 +1600,20 @@ mod tests {
                 assert!(input_amount.currency.equals(&DAI.clone()));
                 assert_eq!(input_amount.quotient(), 100.into());
             }
+
+            #[test]
+            fn token_and_currency_amounts_are_equivalent() {
+                let token_amount = CurrencyAmount::from_raw_amount(DAI.clone(), 98).unwrap();
+                let currency_amount =
+                    CurrencyAmount::from_raw_amount(Currency::Token(DAI.clone()), 98).unwrap();
+                let (from_token, pool_from_token) =
+                    POOL.get_input_amount(&token_amount, None).unwrap();
+                let (from_currency, pool_from_currency) =
+                    POOL.get_input_amount(&currency_amount, None).unwrap();
+                assert!(from_token.currency.equals(&from_currency.currency));
+                assert_eq!(from_token.quotient(), from_currency.quotient());
+                assert_eq!(pool_from_token, pool_from_currency);
+            }
         }
     }
 }
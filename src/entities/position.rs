@@ -3,6 +3,27 @@ use alloy_primitives::{aliases::U48, uint, U160, U256};
 use uniswap_sdk_core::prelude::*;
 use uniswap_v3_sdk::prelude::*;
 
+/// An amount of pool liquidity, as opposed to an amount of either token
+///
+/// Used by [`Position::new_typed`] to make the unit of the liquidity argument explicit at the call
+/// site.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Liquidity(pub u128);
+
+impl From<u128> for Liquidity {
+    #[inline]
+    fn from(liquidity: u128) -> Self {
+        Self(liquidity)
+    }
+}
+
+impl From<Liquidity> for u128 {
+    #[inline]
+    fn from(liquidity: Liquidity) -> Self {
+        liquidity.0
+    }
+}
+
 /// Represents a position on a Uniswap V4 Pool
 #[derive(Clone, Debug)]
 pub struct Position<TP = NoTickDataProvider>
@@ -56,6 +77,130 @@ impl<TP: TickDataProvider> Position<TP> {
         }
     }
 
+    /// Constructs a position like [`Self::new`], taking the liquidity as a [`Liquidity`] so that it
+    /// cannot be confused with a token amount
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: For which pool the liquidity is assigned
+    /// * `liquidity`: The amount of liquidity that is in the position
+    /// * `tick_lower`: The lower tick of the position
+    /// * `tick_upper`: The upper tick of the position
+    #[inline]
+    pub fn new_typed(
+        pool: Pool<TP>,
+        liquidity: Liquidity,
+        tick_lower: TP::Index,
+        tick_upper: TP::Index,
+    ) -> Self {
+        Self::new(pool, liquidity.into(), tick_lower, tick_upper)
+    }
+
+    /// Constructs a position like [`Self::new`], but returns an error instead of panicking when the
+    /// ticks are out of range, out of order or not a multiple of the pool's tick spacing, or the
+    /// liquidity exceeds what a tick can hold, which would revert on-chain
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: For which pool the liquidity is assigned
+    /// * `liquidity`: The amount of liquidity that is in the position
+    /// * `tick_lower`: The lower tick of the position
+    /// * `tick_upper`: The upper tick of the position
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidTickSpacing`] if the pool's tick spacing is not within
+    /// [`MIN_TICK_SPACING`] and [`MAX_TICK_SPACING`], e.g. after the field was modified,
+    /// [`Error::TickOutOfRange`] with the first tick outside of [`MIN_TICK`] and
+    /// [`MAX_TICK`], [`Error::InvalidTickOrder`] if `tick_lower` is not below `tick_upper`,
+    /// [`Error::TickNotUsable`] with the first misaligned tick, and [`Error::LiquidityOverflow`] if
+    /// the liquidity exceeds [`max_liquidity_per_tick`].
+    #[inline]
+    pub fn try_new(
+        pool: Pool<TP>,
+        liquidity: u128,
+        tick_lower: TP::Index,
+        tick_upper: TP::Index,
+    ) -> Result<Self, Error> {
+        let tick_spacing = tick_index_to_i32(pool.tick_spacing);
+        if !(MIN_TICK_SPACING..=MAX_TICK_SPACING).contains(&tick_spacing) {
+            return Err(Error::InvalidTickSpacing { tick_spacing });
+        }
+        let (lower, upper) = (tick_index_to_i32(tick_lower), tick_index_to_i32(tick_upper));
+        for tick in [lower, upper] {
+            if !(MIN_TICK_I32..=MAX_TICK_I32).contains(&tick) {
+                return Err(Error::TickOutOfRange { tick });
+            }
+        }
+        if lower >= upper {
+            return Err(Error::InvalidTickOrder {
+                tick_lower: lower,
+                tick_upper: upper,
+            });
+        }
+        for tick in [tick_lower, tick_upper] {
+            if !(tick % pool.tick_spacing).is_zero() {
+                return Err(Error::TickNotUsable {
+                    tick: tick.to_i24().as_i32(),
+                });
+            }
+        }
+        let max_liquidity = max_liquidity_per_tick(tick_spacing);
+        if liquidity > max_liquidity {
+            return Err(Error::LiquidityOverflow {
+                liquidity,
+                max_liquidity,
+            });
+        }
+        Ok(Self::new(pool, liquidity, tick_lower, tick_upper))
+    }
+
+    /// Returns the pool key of the position's pool
+    #[inline]
+    pub const fn pool_key(&self) -> &PoolKey {
+        &self.pool.pool_key
+    }
+
+    /// Returns a position over the same pool and ticks with a percentage of this position's
+    /// liquidity, e.g. the part of the position being withdrawn
+    ///
+    /// ## Arguments
+    ///
+    /// * `percentage`: The percentage of liquidity to keep
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::PercentageOutOfRange`] if `percentage` is not within 0% and 100%, and
+    /// [`Error::TickOutOfRange`] if a tick does not fit in an `i32`.
+    #[inline]
+    pub fn fraction(&self, percentage: &Percent) -> Result<Position, Error> {
+        if percentage < &Percent::default() || percentage > &Percent::new(1, 1) {
+            return Err(Error::PercentageOutOfRange);
+        }
+        let to_tick = |tick: TP::Index| -> Result<i32, Error> {
+            tick.try_into().map_err(|_| Error::TickOutOfRange {
+                tick: tick_index_to_i32(tick),
+            })
+        };
+        Ok(Position::new(
+            Pool::new(
+                self.pool.currency0.clone(),
+                self.pool.currency1.clone(),
+                self.pool.fee,
+                self.pool.tick_spacing_i24().as_i32(),
+                self.pool.hooks,
+                self.pool.sqrt_price_x96,
+                self.pool.liquidity,
+            )?,
+            (percentage.clone() * Percent::new(self.liquidity, 1))
+                .quotient()
+                .to_u128()
+                .ok_or(Error::PercentageOutOfRange)?,
+            to_tick(self.tick_lower)?,
+            to_tick(self.tick_upper)?,
+        ))
+    }
+
     /// Returns the price of token0 at the lower tick
     #[inline]
     pub fn token0_price_lower(&self) -> Result<Price<Currency, Currency>, Error> {
@@ -221,13 +366,49 @@ impl<TP: TickDataProvider> Position<TP> {
         // these represent the lowest and highest prices that the pool is allowed to "slip" to
         let (sqrt_ratio_x96_lower, sqrt_ratio_x96_upper) =
             self.ratios_after_slippage(slippage_tolerance);
+        self.mint_amounts_within_ratios(sqrt_ratio_x96_lower, sqrt_ratio_x96_upper)
+    }
 
+    /// Returns the maximum amounts that must be sent in order to safely mint the amount of
+    /// liquidity held by the position, with a separate slippage tolerance for each token
+    ///
+    /// ## Note
+    ///
+    /// The maximum amount of token0 is reached when the price slips down, and the maximum amount
+    /// of token1 when it slips up, so `slippage0` bounds the downward move and `slippage1` the
+    /// upward one.
+    ///
+    /// ## Arguments
+    ///
+    /// * `slippage0`: Tolerance of unfavorable slippage from the current price for token0
+    /// * `slippage1`: Tolerance of unfavorable slippage from the current price for token1
+    ///
+    /// ## Returns
+    ///
+    /// The amounts, with slippage
+    #[inline]
+    pub fn mint_amounts_with_slippage_asymmetric(
+        &mut self,
+        slippage0: &Percent,
+        slippage1: &Percent,
+    ) -> Result<MintAmounts, Error> {
+        let (sqrt_ratio_x96_lower, _) = self.ratios_after_slippage(slippage0);
+        let (_, sqrt_ratio_x96_upper) = self.ratios_after_slippage(slippage1);
+        self.mint_amounts_within_ratios(sqrt_ratio_x96_lower, sqrt_ratio_x96_upper)
+    }
+
+    /// Returns the maximum mint amounts for prices slipping between the given sqrt ratios
+    fn mint_amounts_within_ratios(
+        &mut self,
+        sqrt_ratio_x96_lower: U160,
+        sqrt_ratio_x96_upper: U160,
+    ) -> Result<MintAmounts, Error> {
         // construct counterfactual pools from the lower bounded price and the upper bounded price
         let pool_lower = Pool::new(
             self.pool.currency0.clone(),
             self.pool.currency1.clone(),
             self.pool.fee,
-            self.pool.tick_spacing.to_i24().as_i32(),
+            self.pool.tick_spacing_i24().as_i32(),
             self.pool.hooks,
             sqrt_ratio_x96_lower,
             0, // liquidity doesn't matter
@@ -236,7 +417,7 @@ impl<TP: TickDataProvider> Position<TP> {
             self.pool.currency0.clone(),
             self.pool.currency1.clone(),
             self.pool.fee,
-            self.pool.tick_spacing.to_i24().as_i32(),
+            self.pool.tick_spacing_i24().as_i32(),
             self.pool.hooks,
             sqrt_ratio_x96_upper,
             0, // liquidity doesn't matter
@@ -250,7 +431,7 @@ impl<TP: TickDataProvider> Position<TP> {
                 self.pool.currency0.clone(),
                 self.pool.currency1.clone(),
                 self.pool.fee,
-                self.pool.tick_spacing.to_i24().as_i32(),
+                self.pool.tick_spacing_i24().as_i32(),
                 self.pool.hooks,
                 self.pool.sqrt_price_x96,
                 self.pool.liquidity,
@@ -315,7 +496,7 @@ impl<TP: TickDataProvider> Position<TP> {
             self.pool.currency0.clone(),
             self.pool.currency1.clone(),
             self.pool.fee,
-            self.pool.tick_spacing.to_i24().as_i32(),
+            self.pool.tick_spacing_i24().as_i32(),
             self.pool.hooks,
             sqrt_ratio_x96_lower,
             0, // liquidity doesn't matter
@@ -324,7 +505,7 @@ impl<TP: TickDataProvider> Position<TP> {
             self.pool.currency0.clone(),
             self.pool.currency1.clone(),
             self.pool.fee,
-            self.pool.tick_spacing.to_i24().as_i32(),
+            self.pool.tick_spacing_i24().as_i32(),
             self.pool.hooks,
             sqrt_ratio_x96_upper,
             0, // liquidity doesn't matter
@@ -357,13 +538,30 @@ impl<TP: TickDataProvider> Position<TP> {
     /// by the position at the current price for the pool
     #[inline]
     pub fn mint_amounts(&self) -> Result<MintAmounts, Error> {
+        self.amounts_at_current_price(true)
+    }
+
+    /// Returns the amounts of token0 and token1 that this position's liquidity could be burned for
+    /// at the current pool price, without caching
+    #[inline]
+    pub fn current_amounts(&self) -> Result<(U256, U256), Error> {
+        let MintAmounts { amount0, amount1 } = self.amounts_at_current_price(false)?;
+        Ok((amount0, amount1))
+    }
+
+    /// Returns the token amounts of the position's liquidity at the current pool price
+    ///
+    /// ## Arguments
+    ///
+    /// * `round_up`: Whether to round the amounts up, as when minting, or down, as when burning
+    fn amounts_at_current_price(&self, round_up: bool) -> Result<MintAmounts, Error> {
         Ok(if self.pool.tick_current < self.tick_lower {
             MintAmounts {
                 amount0: get_amount_0_delta(
                     get_sqrt_ratio_at_tick(self.tick_lower.to_i24())?,
                     get_sqrt_ratio_at_tick(self.tick_upper.to_i24())?,
                     self.liquidity,
-                    true,
+                    round_up,
                 )?,
                 amount1: U256::ZERO,
             }
@@ -373,13 +571,13 @@ impl<TP: TickDataProvider> Position<TP> {
                     self.pool.sqrt_price_x96,
                     get_sqrt_ratio_at_tick(self.tick_upper.to_i24())?,
                     self.liquidity,
-                    true,
+                    round_up,
                 )?,
                 amount1: get_amount_1_delta(
                     get_sqrt_ratio_at_tick(self.tick_lower.to_i24())?,
                     self.pool.sqrt_price_x96,
                     self.liquidity,
-                    true,
+                    round_up,
                 )?,
             }
         } else {
@@ -389,7 +587,7 @@ impl<TP: TickDataProvider> Position<TP> {
                     get_sqrt_ratio_at_tick(self.tick_lower.to_i24())?,
                     get_sqrt_ratio_at_tick(self.tick_upper.to_i24())?,
                     self.liquidity,
-                    true,
+                    round_up,
                 )?,
             }
         })
@@ -537,4 +735,367 @@ impl<TP: TickDataProvider> Position<TP> {
         // this function always uses full precision
         Self::from_amounts(pool, tick_lower, tick_upper, U256::MAX, amount1, true)
     }
+
+    /// Computes a single-sided position funded with the given amount of token0 at the pool's
+    /// current price
+    ///
+    /// If the range is in range or above the current price, the liquidity is bounded by
+    /// `amount0` and the paired amount of token1, if any, is given by
+    /// [`Position::mint_amounts`]. If the range is entirely below the current price, token0
+    /// cannot be deposited and the position has zero liquidity.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool for which the position is created
+    /// * `tick_lower`: The lower tick
+    /// * `tick_upper`: The upper tick
+    /// * `amount0`: The amount of token0 to deposit
+    #[inline]
+    pub fn from_amount0_with_price(
+        pool: Pool<TP>,
+        tick_lower: TP::Index,
+        tick_upper: TP::Index,
+        amount0: U256,
+    ) -> Result<Self, Error> {
+        if pool.sqrt_price_x96 >= get_sqrt_ratio_at_tick(tick_upper.to_i24())? {
+            return Ok(Self::new(pool, 0, tick_lower, tick_upper));
+        }
+        Self::from_amount0(pool, tick_lower, tick_upper, amount0, true)
+    }
+
+    /// Computes a single-sided position funded with the given amount of token1 at the pool's
+    /// current price
+    ///
+    /// If the range is in range or below the current price, the liquidity is bounded by
+    /// `amount1` and the paired amount of token0, if any, is given by
+    /// [`Position::mint_amounts`]. If the range is entirely above the current price, token1
+    /// cannot be deposited and the position has zero liquidity.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool for which the position is created
+    /// * `tick_lower`: The lower tick
+    /// * `tick_upper`: The upper tick
+    /// * `amount1`: The amount of token1 to deposit
+    #[inline]
+    pub fn from_amount1_with_price(
+        pool: Pool<TP>,
+        tick_lower: TP::Index,
+        tick_upper: TP::Index,
+        amount1: U256,
+    ) -> Result<Self, Error> {
+        if pool.sqrt_price_x96 <= get_sqrt_ratio_at_tick(tick_lower.to_i24())? {
+            return Ok(Self::new(pool, 0, tick_lower, tick_upper));
+        }
+        Self::from_amount1(pool, tick_lower, tick_upper, amount1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn pool_key_delegates_to_pool() {
+        let position = Position::new(USDC_DAI.clone(), ONE_ETHER, -10, 10);
+        assert_eq!(position.pool_key(), &USDC_DAI.pool_key);
+        assert_eq!(
+            position.pool_key(),
+            &Pool::get_pool_key(
+                &USDC.clone().into(),
+                &DAI.clone().into(),
+                FeeAmount::LOWEST.into(),
+                10,
+                Address::ZERO,
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn current_amounts_match_burn_amounts_and_round_below_mint_amounts() {
+        let position = Position::new(USDC_DAI.clone(), ONE_ETHER, -10, 10);
+        let (amount0, amount1) = position.current_amounts().unwrap();
+        assert_eq!(amount0.to_big_int(), position.amount0().unwrap().quotient());
+        assert_eq!(amount1.to_big_int(), position.amount1().unwrap().quotient());
+
+        let MintAmounts {
+            amount0: mint_amount0,
+            amount1: mint_amount1,
+        } = position.mint_amounts().unwrap();
+        assert!(amount0 <= mint_amount0 && mint_amount0 - amount0 <= U256::from(1));
+        assert!(amount1 <= mint_amount1 && mint_amount1 - amount1 <= U256::from(1));
+    }
+
+    #[test]
+    fn fraction_scales_liquidity() {
+        let position = Position::new(USDC_DAI.clone(), ONE_ETHER, -10, 10);
+        let half = position.fraction(&Percent::new(1, 2)).unwrap();
+        assert_eq!(half.liquidity, position.liquidity / 2);
+        assert_eq!(half.pool, position.pool);
+        assert_eq!(half.tick_lower, position.tick_lower);
+        assert_eq!(half.tick_upper, position.tick_upper);
+    }
+
+    #[test]
+    fn new_typed_matches_new() {
+        let typed = Position::new_typed(USDC_DAI.clone(), Liquidity(ONE_ETHER), -10, 10);
+        let untyped = Position::new(USDC_DAI.clone(), ONE_ETHER, -10, 10);
+        assert_eq!(typed.liquidity, ONE_ETHER);
+        assert_eq!(typed.pool, untyped.pool);
+        assert_eq!(typed.tick_lower, untyped.tick_lower);
+        assert_eq!(typed.tick_upper, untyped.tick_upper);
+        assert_eq!(Liquidity::from(ONE_ETHER), Liquidity(ONE_ETHER));
+        assert_eq!(u128::from(Liquidity(ONE_ETHER)), ONE_ETHER);
+    }
+
+    mod fraction {
+        use super::*;
+
+        fn position() -> Position {
+            Position::new(USDC_DAI.clone(), ONE_ETHER, -10, 10)
+        }
+
+        #[test]
+        fn scales_liquidity() {
+            let half = position().fraction(&Percent::new(1, 2)).unwrap();
+            assert_eq!(half.liquidity, ONE_ETHER / 2);
+            assert_eq!(half.tick_lower, -10);
+            assert_eq!(half.tick_upper, 10);
+            assert_eq!(
+                position().fraction(&Percent::new(1, 1)).unwrap().liquidity,
+                ONE_ETHER
+            );
+            assert_eq!(
+                position().fraction(&Percent::default()).unwrap().liquidity,
+                0
+            );
+        }
+
+        #[test]
+        fn rejects_percentage_above_one() {
+            assert!(matches!(
+                position().fraction(&Percent::new(101, 100)),
+                Err(Error::PercentageOutOfRange)
+            ));
+            // would overflow `u128` if scaled
+            let position = Position::new(USDC_DAI.clone(), u128::MAX, -10, 10);
+            assert!(matches!(
+                position.fraction(&Percent::new(2, 1)),
+                Err(Error::PercentageOutOfRange)
+            ));
+        }
+
+        #[test]
+        fn rejects_negative_percentage() {
+            assert!(matches!(
+                position().fraction(&Percent::new(-1, 100)),
+                Err(Error::PercentageOutOfRange)
+            ));
+        }
+    }
+
+    mod try_new {
+        use super::*;
+
+        #[test]
+        fn accepts_aligned_ticks() {
+            let position = Position::try_new(USDC_DAI.clone(), ONE_ETHER, -20, 10).unwrap();
+            assert_eq!(position.tick_lower, -20);
+            assert_eq!(position.tick_upper, 10);
+        }
+
+        #[test]
+        fn rejects_ticks_out_of_order() {
+            assert!(matches!(
+                Position::try_new(USDC_DAI.clone(), ONE_ETHER, 10, -10),
+                Err(Error::InvalidTickOrder {
+                    tick_lower: 10,
+                    tick_upper: -10
+                })
+            ));
+            assert!(matches!(
+                Position::try_new(USDC_DAI.clone(), ONE_ETHER, 10, 10),
+                Err(Error::InvalidTickOrder { .. })
+            ));
+        }
+
+        #[test]
+        fn rejects_ticks_out_of_range() {
+            // the nearest usable ticks past the bounds for a tick spacing of 10
+            let above_max = MAX_TICK_I32 / 10 * 10 + 10;
+            let below_min = MIN_TICK_I32 / 10 * 10 - 10;
+            assert!(matches!(
+                Position::try_new(USDC_DAI.clone(), ONE_ETHER, -10, above_max),
+                Err(Error::TickOutOfRange { tick }) if tick == above_max
+            ));
+            assert!(matches!(
+                Position::try_new(USDC_DAI.clone(), ONE_ETHER, below_min, 10),
+                Err(Error::TickOutOfRange { tick }) if tick == below_min
+            ));
+            assert!(matches!(
+                Position::try_new(USDC_DAI.clone(), ONE_ETHER, -10, i32::MAX),
+                Err(Error::TickOutOfRange { tick: i32::MAX })
+            ));
+        }
+
+        #[test]
+        fn rejects_misaligned_lower_tick() {
+            assert!(matches!(
+                Position::try_new(USDC_DAI.clone(), ONE_ETHER, -15, 10),
+                Err(Error::TickNotUsable { tick: -15 })
+            ));
+        }
+
+        #[test]
+        fn rejects_liquidity_above_max_per_tick() {
+            let max_liquidity = max_liquidity_per_tick(10);
+            assert!(Position::try_new(USDC_DAI.clone(), max_liquidity, -10, 10).is_ok());
+            assert!(matches!(
+                Position::try_new(USDC_DAI.clone(), max_liquidity + 1, -10, 10),
+                Err(Error::LiquidityOverflow { liquidity, max_liquidity: max })
+                    if liquidity == max + 1 && max == max_liquidity
+            ));
+        }
+
+        #[test]
+        fn rejects_invalid_tick_spacing() {
+            for tick_spacing in [0, -10, 1 << 24, i32::MIN] {
+                let mut pool = USDC_DAI.clone();
+                pool.tick_spacing = tick_spacing;
+                assert!(matches!(
+                    Position::try_new(pool, ONE_ETHER, -10, 10),
+                    Err(Error::InvalidTickSpacing { tick_spacing: t }) if t == tick_spacing
+                ));
+            }
+        }
+
+        #[test]
+        fn rejects_liquidity_above_v4_max_but_within_v3_max() {
+            // V3 counts 177455 ticks for a tick spacing of 10, V4 counts 177456
+            let v3_max_liquidity = 1_917_569_901_783_203_986_719_870_431_555_990_u128;
+            let liquidity = max_liquidity_per_tick(10) + 1;
+            assert!(liquidity <= v3_max_liquidity);
+            assert!(matches!(
+                Position::try_new(USDC_DAI.clone(), liquidity, -10, 10),
+                Err(Error::LiquidityOverflow { .. })
+            ));
+        }
+
+        #[test]
+        fn rejects_misaligned_upper_tick() {
+            assert!(matches!(
+                Position::try_new(USDC_DAI.clone(), ONE_ETHER, -10, 11),
+                Err(Error::TickNotUsable { tick: 11 })
+            ));
+        }
+    }
+
+    mod with_price {
+        use super::*;
+
+        const AMOUNT: U256 = uint!(1_000_000_000_000_000_000_U256);
+
+        #[test]
+        fn from_amount0_in_range_needs_both_currencies() {
+            let position =
+                Position::from_amount0_with_price(USDC_DAI.clone(), -100, 100, AMOUNT).unwrap();
+            let MintAmounts { amount0, amount1 } = position.mint_amounts().unwrap();
+            assert!(position.liquidity > 0);
+            assert!(amount0 <= AMOUNT);
+            assert!(AMOUNT - amount0 < U256::from(10));
+            assert!(amount1 > U256::ZERO);
+        }
+
+        #[test]
+        fn from_amount0_below_price_is_single_sided() {
+            let position =
+                Position::from_amount0_with_price(USDC_DAI.clone(), 100, 200, AMOUNT).unwrap();
+            let MintAmounts { amount0, amount1 } = position.mint_amounts().unwrap();
+            assert!(position.liquidity > 0);
+            assert!(amount0 <= AMOUNT);
+            assert!(AMOUNT - amount0 < U256::from(10));
+            assert_eq!(amount1, U256::ZERO);
+        }
+
+        #[test]
+        fn from_amount0_above_price_has_zero_liquidity() {
+            let position =
+                Position::from_amount0_with_price(USDC_DAI.clone(), -200, -100, AMOUNT).unwrap();
+            assert_eq!(position.liquidity, 0);
+        }
+
+        #[test]
+        fn from_amount1_in_range_needs_both_currencies() {
+            let position =
+                Position::from_amount1_with_price(USDC_DAI.clone(), -100, 100, AMOUNT).unwrap();
+            let MintAmounts { amount0, amount1 } = position.mint_amounts().unwrap();
+            assert!(position.liquidity > 0);
+            assert!(amount1 <= AMOUNT);
+            assert!(AMOUNT - amount1 < U256::from(10));
+            assert!(amount0 > U256::ZERO);
+        }
+
+        #[test]
+        fn from_amount1_above_price_is_single_sided() {
+            let position =
+                Position::from_amount1_with_price(USDC_DAI.clone(), -200, -100, AMOUNT).unwrap();
+            let MintAmounts { amount0, amount1 } = position.mint_amounts().unwrap();
+            assert!(position.liquidity > 0);
+            assert!(amount1 <= AMOUNT);
+            assert!(AMOUNT - amount1 < U256::from(10));
+            assert_eq!(amount0, U256::ZERO);
+        }
+
+        #[test]
+        fn from_amount1_below_price_has_zero_liquidity() {
+            let position =
+                Position::from_amount1_with_price(USDC_DAI.clone(), 100, 200, AMOUNT).unwrap();
+            assert_eq!(position.liquidity, 0);
+        }
+    }
+
+    mod mint_amounts_with_slippage_asymmetric {
+        use super::*;
+
+        fn position() -> Position {
+            Position::new(USDC_DAI.clone(), ONE_ETHER, -100, 100)
+        }
+
+        #[test]
+        fn equals_symmetric_for_equal_tolerances() {
+            let slippage_tolerance = Percent::new(5, 100);
+            let asymmetric = position()
+                .mint_amounts_with_slippage_asymmetric(&slippage_tolerance, &slippage_tolerance)
+                .unwrap();
+            let symmetric = position()
+                .mint_amounts_with_slippage(&slippage_tolerance)
+                .unwrap();
+            assert_eq!(asymmetric.amount0, symmetric.amount0);
+            assert_eq!(asymmetric.amount1, symmetric.amount1);
+        }
+
+        #[test]
+        fn applies_each_tolerance_to_its_token() {
+            let tight = Percent::new(1, 1000);
+            let loose = Percent::new(5, 100);
+            let tight_amounts = position().mint_amounts_with_slippage(&tight).unwrap();
+            let loose_amounts = position().mint_amounts_with_slippage(&loose).unwrap();
+            assert!(tight_amounts.amount0 < loose_amounts.amount0);
+            assert!(tight_amounts.amount1 < loose_amounts.amount1);
+
+            let amounts = position()
+                .mint_amounts_with_slippage_asymmetric(&tight, &loose)
+                .unwrap();
+            assert_eq!(amounts.amount0, tight_amounts.amount0);
+            assert_eq!(amounts.amount1, loose_amounts.amount1);
+
+            let amounts = position()
+                .mint_amounts_with_slippage_asymmetric(&loose, &tight)
+                .unwrap();
+            assert_eq!(amounts.amount0, loose_amounts.amount0);
+            assert_eq!(amounts.amount1, tight_amounts.amount1);
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use alloc::vec::Vec;
 use alloy_primitives::ChainId;
-use uniswap_sdk_core::prelude::{BaseCurrency, Currency, Price};
+use uniswap_sdk_core::prelude::{BaseCurrency, Currency, CurrencyAmount, Price};
 use uniswap_v3_sdk::entities::TickDataProvider;
 
 /// Represents a list of pools through which a swap can occur
@@ -121,6 +121,59 @@ where
     }
 }
 
+impl<TInput, TOutput, TP> Route<TInput, TOutput, TP>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: Clone + TickDataProvider,
+{
+    /// Returns the amount out of the route for the given amount in, by simulating the swaps
+    /// without constructing a [`Trade`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `amount_in`: The amount of the input currency to swap
+    #[inline]
+    pub fn quote_exact_input(
+        &self,
+        amount_in: &CurrencyAmount<impl BaseCurrency>,
+    ) -> Result<CurrencyAmount<TOutput>, Error> {
+        assert!(amount_in.currency.equals(&self.input), "INPUT");
+        let mut token_amount = amount_with_path_currency(amount_in, &self.pools[0])?;
+        for pool in &self.pools {
+            (token_amount, _) = pool.get_output_amount(&token_amount, None)?;
+        }
+        Ok(CurrencyAmount::from_fractional_amount(
+            self.output.clone(),
+            token_amount.numerator,
+            token_amount.denominator,
+        )?)
+    }
+
+    /// Returns the amount in required by the route for the given amount out, by simulating the
+    /// swaps without constructing a [`Trade`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `amount_out`: The amount of the output currency to receive
+    #[inline]
+    pub fn quote_exact_output(
+        &self,
+        amount_out: &CurrencyAmount<impl BaseCurrency>,
+    ) -> Result<CurrencyAmount<TInput>, Error> {
+        assert!(amount_out.currency.equals(&self.output), "OUTPUT");
+        let mut token_amount = amount_with_path_currency(amount_out, self.pools.last().unwrap())?;
+        for pool in self.pools.iter().rev() {
+            (token_amount, _) = pool.get_input_amount(&token_amount, None)?;
+        }
+        Ok(CurrencyAmount::from_fractional_amount(
+            self.input.clone(),
+            token_amount.numerator,
+            token_amount.denominator,
+        )?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Pool, Route};
@@ -447,6 +500,35 @@ mod tests {
             assert!(price.quote_currency.equals(&ETHER.clone()));
         }
 
+        #[test]
+        fn correct_for_mixed_decimals() {
+            // 1 DAI (18 decimals) = 1 USDC (6 decimals)
+            let pool = Pool::new(
+                DAI.clone().into(),
+                USDC.clone().into(),
+                FeeAmount::LOWEST.into(),
+                10,
+                Address::ZERO,
+                encode_sqrt_ratio_x96(1_000_000, ONE_ETHER),
+                0,
+            )
+            .unwrap();
+            let route = Route::new(vec![pool.clone()], DAI.clone(), USDC.clone()).unwrap();
+            assert_eq!(route.mid_price().unwrap().to_fixed(4, None), "1.0000");
+            let route = Route::new(vec![pool], USDC.clone(), DAI.clone()).unwrap();
+            assert_eq!(route.mid_price().unwrap().to_fixed(4, None), "1.0000");
+        }
+
+        #[test]
+        fn correct_for_mixed_decimals_at_raw_parity() {
+            // `DAI_USDC` prices 1 wei of DAI at 1 unit of USDC, i.e. 1 DAI = 10^12 USDC
+            let route = Route::new(vec![DAI_USDC.clone()], DAI.clone(), USDC.clone()).unwrap();
+            assert_eq!(
+                route.mid_price().unwrap().to_fixed(4, None),
+                "1000000000000.0000"
+            );
+        }
+
         #[test]
         fn can_be_constructed_with_ether_as_input_on_a_weth_pool() {
             let route =
@@ -487,4 +569,58 @@ mod tests {
             assert_eq!(route.path_output, ETHER.clone().into());
         }
     }
+
+    mod quote {
+        use super::*;
+        use crate::prelude::Trade;
+
+        fn pool_with_liquidity(
+            currency_a: &Currency,
+            currency_b: &Currency,
+        ) -> Pool<TickListDataProvider> {
+            Pool::new_with_tick_data_provider(
+                currency_a.clone(),
+                currency_b.clone(),
+                FeeAmount::MEDIUM.into(),
+                10,
+                Address::ZERO,
+                encode_sqrt_ratio_x96(1, 1),
+                ONE_ETHER,
+                TickListDataProvider::new(TICK_LIST.clone(), 10),
+            )
+            .unwrap()
+        }
+
+        fn route() -> Route<Currency, Currency, TickListDataProvider> {
+            Route::new(
+                vec![
+                    pool_with_liquidity(&CURRENCY0, &CURRENCY1),
+                    pool_with_liquidity(&CURRENCY1, &CURRENCY2),
+                ],
+                CURRENCY0.clone(),
+                CURRENCY2.clone(),
+            )
+            .unwrap()
+        }
+
+        #[test]
+        fn quote_exact_input_matches_trade() {
+            let route = route();
+            let amount_in = CurrencyAmount::from_raw_amount(CURRENCY0.clone(), 1_000_000).unwrap();
+            let amount_out = route.quote_exact_input(&amount_in).unwrap();
+            let trade = Trade::from_route(route, amount_in, TradeType::ExactInput).unwrap();
+            assert_eq!(amount_out, trade.output_amount().unwrap());
+            assert!(amount_out.currency.equals(&CURRENCY2.clone()));
+        }
+
+        #[test]
+        fn quote_exact_output_matches_trade() {
+            let route = route();
+            let amount_out = CurrencyAmount::from_raw_amount(CURRENCY2.clone(), 1_000_000).unwrap();
+            let amount_in = route.quote_exact_output(&amount_out).unwrap();
+            let trade = Trade::from_route(route, amount_out, TradeType::ExactOutput).unwrap();
+            assert_eq!(amount_in, trade.input_amount().unwrap());
+            assert!(amount_in.currency.equals(&CURRENCY0.clone()));
+        }
+    }
 }
@@ -1,5 +1,11 @@
-use crate::prelude::{amount_with_path_currency, Error, Pool, Route};
-use rustc_hash::FxHashSet;
+use crate::prelude::{
+    amount_with_path_currency, format_currency_amount, Error, Pool, PoolKey, Route,
+};
+use alloc::{format, string::String};
+use alloy_primitives::{I256, U256};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use rustc_hash::{FxHashMap, FxHashSet};
 use uniswap_sdk_core::prelude::{sorted_insert::sorted_insert, *};
 use uniswap_v3_sdk::prelude::*;
 
@@ -64,12 +70,26 @@ where
     }
 }
 
+/// Memoized swap results keyed by the pool key, the direction (`zero_for_one`) and the amount
+/// specified, positive for an exact input and negative for an exact output, as in the pool's
+/// `swap`. The value is the computed amount on the other side of the swap.
+///
+/// Entries are only valid for the pool states they were computed against, so a cache must not be
+/// reused once any of its pools has been updated.
+pub type SwapCache = FxHashMap<(PoolKey, bool, I256), CurrencyAmount<Currency>>;
+
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct BestTradeOptions {
     /// how many results to return
     pub max_num_results: Option<usize>,
     /// the maximum number of hops a trade should contain
     pub max_hops: Option<usize>,
+    /// the minimum raw output amount of a trade returned by [`Trade::best_trade_exact_in`], below
+    /// which trades are discarded as dust
+    pub min_output: Option<u128>,
+    /// the maximum raw input amount of a trade returned by [`Trade::best_trade_exact_out`], above
+    /// which trades are discarded as too expensive
+    pub max_input: Option<u128>,
 }
 
 /// Represents a swap through a route
@@ -122,6 +142,12 @@ where
     pub fn output_currency(&self) -> &TOutput {
         &self.output_amount.currency
     }
+
+    /// The price of the swap expressed in terms of output amount/input amount.
+    #[inline]
+    pub fn execution_price(&self) -> Price<TInput, TOutput> {
+        Price::from_currency_amounts(self.input_amount.clone(), self.output_amount.clone())
+    }
 }
 
 /// Represents a trade executed against a set of routes where some percentage of the input is split
@@ -151,6 +177,8 @@ where
     _execution_price: Option<Price<TInput, TOutput>>,
     /// The cached result of the price impact computation
     _price_impact: Option<Percent>,
+    /// The transfer tax allowed on the output of a trade involving fee-on-transfer tokens
+    _fee_on_transfer_tax: Option<Percent>,
 }
 
 impl<TInput, TOutput, TP> Trade<TInput, TOutput, TP>
@@ -167,9 +195,17 @@ where
     /// * `trade_type`: The type of trade, exact input or exact output
     #[inline]
     fn new(swaps: Vec<Swap<TInput, TOutput, TP>>, trade_type: TradeType) -> Result<Self, Error> {
+        if swaps.is_empty() {
+            return Err(Error::EmptyTrade);
+        }
         let input_currency = swaps[0].input_currency();
         let output_currency = swaps[0].output_currency();
         for Swap { route, .. } in &swaps {
+            if mixes_native_and_wrapped(input_currency, &route.input)
+                || mixes_native_and_wrapped(output_currency, &route.output)
+            {
+                return Err(Error::InconsistentNativeUsage);
+            }
             assert!(input_currency.equals(&route.input), "INPUT_CURRENCY_MATCH");
             assert!(
                 output_currency.equals(&route.output),
@@ -184,14 +220,13 @@ where
             .iter()
             .flat_map(|swap| swap.route.pools.iter())
             .map(|pool| {
-                Pool::get_pool_id(
+                Pool::pool_id_unchecked(
                     &pool.currency0,
                     &pool.currency1,
                     pool.fee,
                     pool.tick_spacing,
                     pool.hooks,
                 )
-                .unwrap()
             });
         let pool_id_set = FxHashSet::from_iter(pool_ids);
         assert_eq!(num_pools, pool_id_set.len(), "POOLS_DUPLICATED");
@@ -202,6 +237,7 @@ where
             _output_amount: None,
             _execution_price: None,
             _price_impact: None,
+            _fee_on_transfer_tax: None,
         })
     }
 
@@ -230,6 +266,57 @@ where
         Self::new(swaps, trade_type)
     }
 
+    /// Creates a trade from the result of an on-chain quote, e.g. a `V4Quoter` call that accounts
+    /// for the behavior of the pools' hooks, without simulating the route locally
+    ///
+    /// ## Arguments
+    ///
+    /// * `route`: The route that was quoted
+    /// * `amount`: The quoted exact amount, the input amount of an exact input trade or the output
+    ///   amount of an exact output trade
+    /// * `quoted_amount`: The raw amount returned by the quoter, the output amount of an exact
+    ///   input trade or the input amount of an exact output trade
+    /// * `trade_type`: The type of the quoted trade
+    #[inline]
+    pub fn from_quote(
+        route: Route<TInput, TOutput, TP>,
+        amount: CurrencyAmount<impl BaseCurrency>,
+        quoted_amount: U256,
+        trade_type: TradeType,
+    ) -> Result<Self, Error> {
+        let (input_amount, output_amount) = match trade_type {
+            TradeType::ExactInput => {
+                assert!(amount.currency.equals(&route.input), "INPUT");
+                (
+                    CurrencyAmount::from_fractional_amount(
+                        route.input.clone(),
+                        amount.numerator,
+                        amount.denominator,
+                    )?,
+                    CurrencyAmount::from_raw_amount(
+                        route.output.clone(),
+                        quoted_amount.to_big_int(),
+                    )?,
+                )
+            }
+            TradeType::ExactOutput => {
+                assert!(amount.currency.equals(&route.output), "OUTPUT");
+                (
+                    CurrencyAmount::from_raw_amount(
+                        route.input.clone(),
+                        quoted_amount.to_big_int(),
+                    )?,
+                    CurrencyAmount::from_fractional_amount(
+                        route.output.clone(),
+                        amount.numerator,
+                        amount.denominator,
+                    )?,
+                )
+            }
+        };
+        Self::create_unchecked_trade(route, input_amount, output_amount, trade_type)
+    }
+
     /// When the trade consists of just a single route, this returns the route of the trade.
     #[inline]
     pub fn route(&self) -> &Route<TInput, TOutput, TP> {
@@ -238,6 +325,10 @@ where
     }
 
     /// Returns the input currency of the swap
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the trade has no swaps
     #[inline]
     pub fn input_currency(&self) -> &TInput {
         self.swaps[0].input_currency()
@@ -246,6 +337,9 @@ where
     /// The input amount for the trade assuming no slippage.
     #[inline]
     pub fn input_amount(&self) -> Result<CurrencyAmount<TInput>, Error> {
+        if self.swaps.is_empty() {
+            return Err(Error::EmptyTrade);
+        }
         let mut total = Fraction::default();
         for Swap { input_amount, .. } in &self.swaps {
             total = total + input_amount.as_fraction();
@@ -270,6 +364,10 @@ where
     }
 
     /// Returns the output currency of the swap
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the trade has no swaps
     #[inline]
     pub fn output_currency(&self) -> &TOutput {
         self.swaps[0].output_currency()
@@ -278,6 +376,9 @@ where
     /// The output amount for the trade assuming no slippage.
     #[inline]
     pub fn output_amount(&self) -> Result<CurrencyAmount<TOutput>, Error> {
+        if self.swaps.is_empty() {
+            return Err(Error::EmptyTrade);
+        }
         let mut total = Fraction::default();
         for Swap { output_amount, .. } in &self.swaps {
             total = total + output_amount.as_fraction();
@@ -372,9 +473,111 @@ where
         Ok(self._price_impact.clone().unwrap())
     }
 
+    /// Returns a one-line summary of the trade, e.g.
+    /// `"exactIn 100 USDC -> 99.5 DAI via 2 pools, impact 0.1%"`
+    #[inline]
+    pub fn summary(&self) -> Result<String, Error> {
+        let trade_type = match self.trade_type {
+            TradeType::ExactInput => "exactIn",
+            TradeType::ExactOutput => "exactOut",
+        };
+        let num_pools = self
+            .swaps
+            .iter()
+            .map(|swap| swap.route.pools.len())
+            .sum::<usize>();
+        Ok(format!(
+            "{trade_type} {} -> {} via {num_pools} {}, impact {}%",
+            format_currency_amount(&self.input_amount()?),
+            format_currency_amount(&self.output_amount()?),
+            if num_pools == 1 { "pool" } else { "pools" },
+            self.price_impact()?.to_significant(3, None)?,
+        ))
+    }
+
+    /// Returns the output amount of the trade expressed in a reference currency, e.g. a USD
+    /// notional
+    ///
+    /// ## Arguments
+    ///
+    /// * `price`: The price of the output currency in terms of the reference currency
+    #[inline]
+    pub fn value_in(
+        &self,
+        price: &Price<TOutput, Currency>,
+    ) -> Result<CurrencyAmount<Currency>, Error> {
+        Ok(price.quote(&self.output_amount()?)?)
+    }
+
+    /// Marks the trade as involving fee-on-transfer tokens, which deliver less than the nominal
+    /// amount of a transfer, so that the minimum amount out also allows for the transfer tax
+    ///
+    /// The slippage-based minimum of [`Self::minimum_amount_out`] is further reduced by
+    /// `transfer_tax`, so slippage protection is kept on top of the tax. The tax should be the
+    /// total taken by the transfers along the trade, e.g. from the token's documentation or a
+    /// simulation.
+    ///
+    /// ## Arguments
+    ///
+    /// * `transfer_tax`: The portion of the output amount allowed to be lost to transfer taxes
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::FeeOnTransferExactOutUnsupported`] for an exact output trade, as the
+    /// amount in required for an exact amount out cannot be known.
+    #[inline]
+    pub fn with_fee_on_transfer_support(mut self, transfer_tax: Percent) -> Result<Self, Error> {
+        assert!(
+            transfer_tax >= Percent::default() && transfer_tax < Percent::new(1, 1),
+            "TRANSFER_TAX"
+        );
+        if self.trade_type == TradeType::ExactOutput {
+            return Err(Error::FeeOnTransferExactOutUnsupported);
+        }
+        self._fee_on_transfer_tax = Some(transfer_tax);
+        Ok(self)
+    }
+
+    /// Returns whether the trade supports fee-on-transfer tokens, see
+    /// [`Self::with_fee_on_transfer_support`]
+    #[inline]
+    pub const fn supports_fee_on_transfer(&self) -> bool {
+        self._fee_on_transfer_tax.is_some()
+    }
+
+    /// Returns the transfer tax allowed for a trade with
+    /// [fee-on-transfer support](Self::with_fee_on_transfer_support)
+    #[inline]
+    pub const fn fee_on_transfer_tax(&self) -> Option<&Percent> {
+        self._fee_on_transfer_tax.as_ref()
+    }
+
+    /// Applies the slippage tolerance and the transfer tax, if any, to the output amount of an
+    /// exact input trade
+    fn apply_minimum_out(
+        &self,
+        output_amount: &CurrencyAmount<TOutput>,
+        slippage_tolerance: Percent,
+    ) -> Result<CurrencyAmount<TOutput>, Error> {
+        let minimum_amount_out =
+            output_amount.multiply(&((Percent::new(1, 1) + slippage_tolerance).invert()))?;
+        match &self._fee_on_transfer_tax {
+            Some(transfer_tax) => {
+                Ok(minimum_amount_out.multiply(&(Percent::new(1, 1) - transfer_tax.clone()))?)
+            }
+            None => Ok(minimum_amount_out),
+        }
+    }
+
     /// Get the minimum amount that must be received from this trade for the given slippage
     /// tolerance
     ///
+    /// For exact input trades the output amount is divided by `1 + slippage_tolerance`. The
+    /// result is kept as an exact fraction, and its [`quotient`](CurrencyAmount::quotient), which
+    /// is what gets encoded on-chain, rounds down, so the minimum is never overstated. For trades
+    /// with [fee-on-transfer support](Self::with_fee_on_transfer_support), the minimum is further
+    /// reduced by the transfer tax.
+    ///
     /// ## Arguments
     ///
     /// * `slippage_tolerance`: The tolerance of unfavorable slippage from the execution price of
@@ -394,14 +597,18 @@ where
         if self.trade_type == TradeType::ExactOutput {
             return Ok(output_amount);
         }
-        output_amount
-            .multiply(&((Percent::new(1, 1) + slippage_tolerance).invert()))
-            .map_err(|e| e.into())
+        self.apply_minimum_out(&output_amount, slippage_tolerance)
     }
 
     /// Get the minimum amount that must be received from this trade for the given slippage
     /// tolerance
     ///
+    /// For exact input trades the output amount is divided by `1 + slippage_tolerance`. The
+    /// result is kept as an exact fraction, and its [`quotient`](CurrencyAmount::quotient), which
+    /// is what gets encoded on-chain, rounds down, so the minimum is never overstated. For trades
+    /// with [fee-on-transfer support](Self::with_fee_on_transfer_support), the minimum is further
+    /// reduced by the transfer tax.
+    ///
     /// ## Arguments
     ///
     /// * `slippage_tolerance`: The tolerance of unfavorable slippage from the execution price of
@@ -421,9 +628,7 @@ where
         if self.trade_type == TradeType::ExactOutput {
             return Ok(output_amount);
         }
-        output_amount
-            .multiply(&((Percent::new(1, 1) + slippage_tolerance).invert()))
-            .map_err(|e| e.into())
+        self.apply_minimum_out(&output_amount, slippage_tolerance)
     }
 
     /// Get the maximum amount in that can be spent via this trade for the given slippage tolerance
@@ -509,6 +714,38 @@ where
             self.minimum_amount_out_cached(slippage_tolerance, None)?,
         ))
     }
+
+    /// Return the execution price of each swap after accounting for slippage tolerance, in the
+    /// order of [`Self::swaps`]
+    ///
+    /// Unlike [`Self::worst_execution_price`], which is based on the aggregate amounts of the
+    /// trade, the slippage tolerance is applied to the amounts of each route, so the worst price
+    /// of each route of a split trade can be checked on its own.
+    ///
+    /// ## Arguments
+    ///
+    /// * `slippage_tolerance`: The allowed tolerated slippage
+    #[inline]
+    pub fn per_route_worst_prices(
+        &self,
+        slippage_tolerance: Percent,
+    ) -> Result<Vec<Price<TInput, TOutput>>, Error> {
+        self.swaps
+            .iter()
+            .map(|swap| {
+                Ok(Price::from_currency_amounts(
+                    self.maximum_amount_in(
+                        slippage_tolerance.clone(),
+                        Some(swap.input_amount.clone()),
+                    )?,
+                    self.minimum_amount_out(
+                        slippage_tolerance.clone(),
+                        Some(swap.output_amount.clone()),
+                    )?,
+                ))
+            })
+            .collect()
+    }
 }
 
 impl<TInput, TOutput, TP> Trade<TInput, TOutput, TP>
@@ -545,6 +782,44 @@ where
         Self::from_route(route, amount_out, TradeType::ExactOutput)
     }
 
+    /// Constructs an exact in trade with the given amount in and route, along with the minimum
+    /// amount out for the given slippage tolerance
+    ///
+    /// ## Arguments
+    ///
+    /// * `route`: The route of the exact in trade
+    /// * `amount_in`: The amount being passed in
+    /// * `slippage_tolerance`: The tolerance of unfavorable slippage from the execution price
+    #[inline]
+    pub fn exact_in_with_slippage(
+        route: Route<TInput, TOutput, TP>,
+        amount_in: CurrencyAmount<impl BaseCurrency>,
+        slippage_tolerance: Percent,
+    ) -> Result<(Self, CurrencyAmount<TOutput>), Error> {
+        let trade = Self::exact_in(route, amount_in)?;
+        let minimum_amount_out = trade.minimum_amount_out(slippage_tolerance, None)?;
+        Ok((trade, minimum_amount_out))
+    }
+
+    /// Constructs an exact out trade with the given amount out and route, along with the maximum
+    /// amount in for the given slippage tolerance
+    ///
+    /// ## Arguments
+    ///
+    /// * `route`: The route of the exact out trade
+    /// * `amount_out`: The amount returned by the trade
+    /// * `slippage_tolerance`: The tolerance of unfavorable slippage from the execution price
+    #[inline]
+    pub fn exact_out_with_slippage(
+        route: Route<TInput, TOutput, TP>,
+        amount_out: CurrencyAmount<impl BaseCurrency>,
+        slippage_tolerance: Percent,
+    ) -> Result<(Self, CurrencyAmount<TInput>), Error> {
+        let trade = Self::exact_out(route, amount_out)?;
+        let maximum_amount_in = trade.maximum_amount_in(slippage_tolerance, None)?;
+        Ok((trade, maximum_amount_in))
+    }
+
     /// Constructs a trade by simulating swaps through the given route
     ///
     /// ## Arguments
@@ -557,6 +832,37 @@ where
         route: Route<TInput, TOutput, TP>,
         amount: CurrencyAmount<impl BaseCurrency>,
         trade_type: TradeType,
+    ) -> Result<Self, Error> {
+        Self::from_route_with_optional_cache(route, amount, trade_type, None)
+    }
+
+    /// Constructs a trade like [`Self::from_route`], reusing the results of swaps already
+    /// simulated through the same pools for the same amounts
+    ///
+    /// Useful when constructing many candidate trades that share hops, e.g. evaluating routes for
+    /// several amounts. See [`SwapCache`] for when a cache can be reused.
+    ///
+    /// ## Arguments
+    ///
+    /// * `route`: The route to swap through
+    /// * `amount`: The amount specified, either input or output, depending on `trade_type`
+    /// * `trade_type`: Whether the trade is an exact input or exact output swap
+    /// * `cache`: The swap results to consult and populate
+    #[inline]
+    pub fn from_route_with_cache(
+        route: Route<TInput, TOutput, TP>,
+        amount: CurrencyAmount<impl BaseCurrency>,
+        trade_type: TradeType,
+        cache: &mut SwapCache,
+    ) -> Result<Self, Error> {
+        Self::from_route_with_optional_cache(route, amount, trade_type, Some(cache))
+    }
+
+    fn from_route_with_optional_cache(
+        route: Route<TInput, TOutput, TP>,
+        amount: CurrencyAmount<impl BaseCurrency>,
+        trade_type: TradeType,
+        mut cache: Option<&mut SwapCache>,
     ) -> Result<Self, Error> {
         let input_amount: CurrencyAmount<TInput>;
         let output_amount: CurrencyAmount<TOutput>;
@@ -566,7 +872,7 @@ where
                 // Account for trades that wrap/unwrap as a first step
                 let mut token_amount = amount_with_path_currency(&amount, &route.pools[0])?;
                 for pool in &route.pools {
-                    (token_amount, _) = pool.get_output_amount(&token_amount, None)?;
+                    token_amount = cached_swap(pool, &token_amount, true, cache.as_deref_mut())?;
                 }
                 output_amount = CurrencyAmount::from_fractional_amount(
                     route.output.clone(),
@@ -585,7 +891,7 @@ where
                 let mut token_amount =
                     amount_with_path_currency(&amount, route.pools.last().unwrap())?;
                 for pool in route.pools.iter().rev() {
-                    (token_amount, _) = pool.get_input_amount(&token_amount, None)?;
+                    token_amount = cached_swap(pool, &token_amount, false, cache.as_deref_mut())?;
                 }
                 input_amount = CurrencyAmount::from_fractional_amount(
                     route.input.clone(),
@@ -605,6 +911,34 @@ where
         )
     }
 
+    /// Constructs a trade by simulating swaps through the given route, inferring the trade type
+    /// from the currency of the amount
+    ///
+    /// ## Arguments
+    ///
+    /// * `route`: The route to swap through
+    /// * `amount`: The amount specified, in either the input currency for an exact input trade or
+    ///   the output currency for an exact output trade
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::AmountCurrencyNotInRoute`] if the amount is denominated in neither the
+    /// input nor the output currency of the route.
+    #[inline]
+    pub fn from_route_auto(
+        route: Route<TInput, TOutput, TP>,
+        amount: CurrencyAmount<impl BaseCurrency>,
+    ) -> Result<Self, Error> {
+        let trade_type = if amount.currency.equals(&route.input) {
+            TradeType::ExactInput
+        } else if amount.currency.equals(&route.output) {
+            TradeType::ExactOutput
+        } else {
+            return Err(Error::AmountCurrencyNotInRoute);
+        };
+        Self::from_route(route, amount, trade_type)
+    }
+
     /// Constructs a trade from routes by simulating swaps
     ///
     /// ## Arguments
@@ -628,6 +962,34 @@ where
         Self::new(populated_routes, trade_type)
     }
 
+    /// Constructs a trade by simulating a swap of each amount through its route
+    ///
+    /// Unlike [`Self::create_unchecked_trade_with_multiple_routes`], the amounts on the other
+    /// side of each swap are computed by simulation. This is equivalent to [`Self::from_routes`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `swaps`: The amount to swap through each route, either input or output depending on
+    ///   `trade_type`
+    /// * `trade_type`: Whether the trade is an exact input or exact output swap
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::EmptyTrade`] if `swaps` is empty.
+    #[inline]
+    pub fn from_swaps(
+        swaps: Vec<(
+            CurrencyAmount<impl BaseCurrency>,
+            Route<TInput, TOutput, TP>,
+        )>,
+        trade_type: TradeType,
+    ) -> Result<Self, Error> {
+        if swaps.is_empty() {
+            return Err(Error::EmptyTrade);
+        }
+        Self::from_routes(swaps, trade_type)
+    }
+
     /// Given a list of pools, and a fixed amount in, returns the top `max_num_results` trades that
     /// go from an input token amount to an output token, making at most `max_hops` hops.
     ///
@@ -643,91 +1005,152 @@ where
     /// * `currency_out`: The desired currency out
     /// * `best_trade_options`: Maximum number of results to return and maximum number of hops a
     ///   returned trade can make, e.g. 1 hop goes through a single pool
-    /// * `current_pools`: Used in recursion; the current list of pools
-    /// * `next_amount_in`: Used in recursion; the original value of the currency_amount_in
-    ///   parameter
-    /// * `best_trades`: Used in recursion; the current list of best trades
     #[inline]
-    #[allow(clippy::needless_pass_by_value)]
-    pub fn best_trade_exact_in<'a>(
-        pools: Vec<Pool<TP>>,
-        currency_amount_in: &'a CurrencyAmount<TInput>,
-        currency_out: &'a TOutput,
+    pub fn best_trade_exact_in(
+        pools: &[Pool<TP>],
+        currency_amount_in: &CurrencyAmount<TInput>,
+        currency_out: &TOutput,
         best_trade_options: BestTradeOptions,
-        current_pools: Vec<Pool<TP>>,
-        next_amount_in: Option<&'a CurrencyAmount<Currency>>,
-        best_trades: &'a mut Vec<Self>,
-    ) -> Result<&'a mut Vec<Self>, Error> {
+    ) -> Result<Vec<Self>, Error> {
         assert!(!pools.is_empty(), "POOLS");
         let max_num_results = best_trade_options.max_num_results.unwrap_or(3);
         let max_hops = best_trade_options.max_hops.unwrap_or(3);
         assert!(max_hops > 0, "MAX_HOPS");
-        if next_amount_in.is_some() {
-            assert!(!current_pools.is_empty(), "INVALID_RECURSION");
-        }
+        let mut best_trades = Vec::new();
+        Self::best_trade_exact_in_recursive(
+            pools,
+            currency_amount_in,
+            currency_out,
+            max_num_results,
+            max_hops,
+            best_trade_options.min_output,
+            &[],
+            &to_currency_amount(currency_amount_in)?,
+            &mut best_trades,
+        )?;
+        Ok(best_trades)
+    }
+
+    /// Recursive step of [`Trade::best_trade_exact_in`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `pools`: The pools not yet used in the current path
+    /// * `currency_amount_in`: The exact amount of input currency to spend
+    /// * `currency_out`: The desired currency out
+    /// * `max_num_results`: Maximum number of results to return
+    /// * `max_hops`: Maximum number of hops remaining for the current path
+    /// * `min_output`: Minimum raw output amount of a returned trade
+    /// * `current_pools`: The pools of the current path
+    /// * `amount_in`: The amount flowing into the next hop of the current path
+    /// * `best_trades`: The current list of best trades
+    #[allow(clippy::too_many_arguments)]
+    fn best_trade_exact_in_recursive(
+        pools: &[Pool<TP>],
+        currency_amount_in: &CurrencyAmount<TInput>,
+        currency_out: &TOutput,
+        max_num_results: usize,
+        max_hops: usize,
+        min_output: Option<u128>,
+        current_pools: &[Pool<TP>],
+        amount_in: &CurrencyAmount<Currency>,
+        best_trades: &mut Vec<Self>,
+    ) -> Result<(), Error> {
         for i in 0..pools.len() {
-            let pool = &pools[i];
-            // pool irrelevant
-            match next_amount_in {
-                Some(amount_in) => {
-                    if !pool.involves_token(&amount_in.currency) {
-                        continue;
-                    }
-                }
-                None => {
-                    if !pool.involves_token(&currency_amount_in.currency) {
-                        continue;
-                    }
-                }
+            Self::best_trade_exact_in_branch(
+                pools,
+                i,
+                currency_amount_in,
+                currency_out,
+                max_num_results,
+                max_hops,
+                min_output,
+                current_pools,
+                amount_in,
+                best_trades,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Explores the paths of [`Trade::best_trade_exact_in_recursive`] that continue through
+    /// `pools[i]`
+    ///
+    /// ## Arguments
+    ///
+    /// * `pools`: The pools not yet used in the current path
+    /// * `i`: The index of the pool to continue the current path through
+    /// * `currency_amount_in`: The exact amount of input currency to spend
+    /// * `currency_out`: The desired currency out
+    /// * `max_num_results`: Maximum number of results to return
+    /// * `max_hops`: Maximum number of hops remaining for the current path
+    /// * `min_output`: Minimum raw output amount of a returned trade
+    /// * `current_pools`: The pools of the current path
+    /// * `amount_in`: The amount flowing into the next hop of the current path
+    /// * `best_trades`: The current list of best trades
+    #[allow(clippy::too_many_arguments)]
+    fn best_trade_exact_in_branch(
+        pools: &[Pool<TP>],
+        i: usize,
+        currency_amount_in: &CurrencyAmount<TInput>,
+        currency_out: &TOutput,
+        max_num_results: usize,
+        max_hops: usize,
+        min_output: Option<u128>,
+        current_pools: &[Pool<TP>],
+        amount_in: &CurrencyAmount<Currency>,
+        best_trades: &mut Vec<Self>,
+    ) -> Result<(), Error> {
+        let pool = &pools[i];
+        // pool irrelevant
+        if !pool.involves_token(&amount_in.currency) {
+            return Ok(());
+        }
+        let amount_out = match pool.get_output_amount(amount_in, None) {
+            Ok((amount_out, _)) => amount_out,
+            Err(Error::InsufficientLiquidity) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let mut next_pools = current_pools.to_vec();
+        next_pools.push(pool.clone());
+        // we have arrived at the output token, so this is the final trade of one of the paths
+        if amount_out.currency.equals(currency_out) {
+            // discard dust trades below the output threshold
+            if min_output.is_some_and(|min_output| amount_out.quotient() < BigInt::from(min_output))
+            {
+                return Ok(());
             }
-            let amount_out = match next_amount_in {
-                Some(amount_in) => pool.get_output_amount(amount_in, None),
-                None => pool.get_output_amount(currency_amount_in, None),
-            };
-            let amount_out = match amount_out {
-                Ok((amount_out, _)) => amount_out,
-                Err(Error::InsufficientLiquidity) => continue,
-                Err(e) => return Err(e),
-            };
-            // we have arrived at the output token, so this is the final trade of one of the paths
-            if amount_out.currency.equals(currency_out) {
-                let mut next_pools = current_pools.clone();
-                next_pools.push(pool.clone());
-                let trade = Self::from_route(
-                    Route::new(
-                        next_pools,
-                        currency_amount_in.currency.clone(),
-                        currency_out.clone(),
-                    )?,
-                    currency_amount_in.clone(),
-                    TradeType::ExactInput,
-                )?;
-                sorted_insert(best_trades, trade, max_num_results, trade_comparator);
-            } else if max_hops > 1 && pools.len() > 1 {
-                let pools_excluding_this_pool = pools[..i]
-                    .iter()
-                    .chain(pools[i + 1..].iter())
-                    .cloned()
-                    .collect();
-                // otherwise, consider all the other paths that lead from this token as long as we
-                // have not exceeded maxHops
-                let mut next_pools = current_pools.clone();
-                next_pools.push(pool.clone());
-                Self::best_trade_exact_in(
-                    pools_excluding_this_pool,
-                    currency_amount_in,
-                    currency_out,
-                    BestTradeOptions {
-                        max_num_results: Some(max_num_results),
-                        max_hops: Some(max_hops - 1),
-                    },
+            let trade = Self::from_route(
+                Route::new(
                     next_pools,
-                    Some(&amount_out),
-                    best_trades,
-                )?;
-            }
+                    currency_amount_in.currency.clone(),
+                    currency_out.clone(),
+                )?,
+                currency_amount_in.clone(),
+                TradeType::ExactInput,
+            )?;
+            sorted_insert(best_trades, trade, max_num_results, trade_comparator);
+        } else if max_hops > 1 && pools.len() > 1 {
+            let pools_excluding_this_pool: Vec<Pool<TP>> = pools[..i]
+                .iter()
+                .chain(pools[i + 1..].iter())
+                .cloned()
+                .collect();
+            // otherwise, consider all the other paths that lead from this token as long as we
+            // have not exceeded maxHops
+            Self::best_trade_exact_in_recursive(
+                &pools_excluding_this_pool,
+                currency_amount_in,
+                currency_out,
+                max_num_results,
+                max_hops - 1,
+                min_output,
+                &next_pools,
+                &amount_out,
+                best_trades,
+            )?;
         }
-        Ok(best_trades)
+        Ok(())
     }
 
     /// Given a list of pools, and a fixed amount out, returns the top `max_num_results` trades that
@@ -745,55 +1168,76 @@ where
     /// * `currency_amount_out`: The desired currency amount out
     /// * `best_trade_options`: Maximum number of results to return and maximum number of hops a
     ///   returned trade can make, e.g. 1 hop goes through a single pool
-    /// * `current_pools`: Used in recursion; the current list of pools
-    /// * `next_amount_out`: Used in recursion; the exact amount of currency out
-    /// * `best_trades`: Used in recursion; the current list of best trades
     #[inline]
-    #[allow(clippy::needless_pass_by_value)]
-    pub fn best_trade_exact_out<'a>(
-        pools: Vec<Pool<TP>>,
-        currency_in: &'a TInput,
-        currency_amount_out: &'a CurrencyAmount<TOutput>,
+    pub fn best_trade_exact_out(
+        pools: &[Pool<TP>],
+        currency_in: &TInput,
+        currency_amount_out: &CurrencyAmount<TOutput>,
         best_trade_options: BestTradeOptions,
-        current_pools: Vec<Pool<TP>>,
-        next_amount_out: Option<&'a CurrencyAmount<Currency>>,
-        best_trades: &'a mut Vec<Self>,
-    ) -> Result<&'a mut Vec<Self>, Error> {
+    ) -> Result<Vec<Self>, Error> {
         assert!(!pools.is_empty(), "POOLS");
         let max_num_results = best_trade_options.max_num_results.unwrap_or(3);
         let max_hops = best_trade_options.max_hops.unwrap_or(3);
         assert!(max_hops > 0, "MAX_HOPS");
-        if next_amount_out.is_some() {
-            assert!(!current_pools.is_empty(), "INVALID_RECURSION");
-        }
-        for i in 0..pools.len() {
-            let pool = &pools[i];
+        let mut best_trades = Vec::new();
+        Self::best_trade_exact_out_recursive(
+            pools,
+            currency_in,
+            currency_amount_out,
+            max_num_results,
+            max_hops,
+            best_trade_options.max_input,
+            &[],
+            &to_currency_amount(currency_amount_out)?,
+            &mut best_trades,
+        )?;
+        Ok(best_trades)
+    }
+
+    /// Recursive step of [`Trade::best_trade_exact_out`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `pools`: The pools not yet used in the current path
+    /// * `currency_in`: The currency to spend
+    /// * `currency_amount_out`: The desired currency amount out
+    /// * `max_num_results`: Maximum number of results to return
+    /// * `max_hops`: Maximum number of hops remaining for the current path
+    /// * `max_input`: Maximum raw input amount of a returned trade
+    /// * `current_pools`: The pools of the current path
+    /// * `amount_out`: The amount flowing out of the previous hop of the current path
+    /// * `best_trades`: The current list of best trades
+    #[allow(clippy::too_many_arguments)]
+    fn best_trade_exact_out_recursive(
+        pools: &[Pool<TP>],
+        currency_in: &TInput,
+        currency_amount_out: &CurrencyAmount<TOutput>,
+        max_num_results: usize,
+        max_hops: usize,
+        max_input: Option<u128>,
+        current_pools: &[Pool<TP>],
+        amount_out: &CurrencyAmount<Currency>,
+        best_trades: &mut Vec<Self>,
+    ) -> Result<(), Error> {
+        for (i, pool) in pools.iter().enumerate() {
             // pool irrelevant
-            match next_amount_out {
-                Some(amount_out) => {
-                    if !pool.involves_token(&amount_out.currency) {
-                        continue;
-                    }
-                }
-                None => {
-                    if !pool.involves_token(&currency_amount_out.currency) {
-                        continue;
-                    }
-                }
+            if !pool.involves_token(&amount_out.currency) {
+                continue;
             }
-            let amount_in = match next_amount_out {
-                Some(amount_out) => pool.get_input_amount(amount_out, None),
-                None => pool.get_input_amount(currency_amount_out, None),
-            };
-            let amount_in = match amount_in {
+            let amount_in = match pool.get_input_amount(amount_out, None) {
                 Ok((amount_in, _)) => amount_in,
                 Err(Error::InsufficientLiquidity) => continue,
                 Err(e) => return Err(e),
             };
+            let mut next_pools = vec![pool.clone()];
+            next_pools.extend_from_slice(current_pools);
             // we have arrived at the input token, so this is the first trade of one of the paths
             if amount_in.currency.equals(currency_in) {
-                let mut next_pools = vec![pool.clone()];
-                next_pools.extend(current_pools.clone());
+                // discard trades requiring more than the input cap
+                if max_input.is_some_and(|max_input| amount_in.quotient() > BigInt::from(max_input))
+                {
+                    continue;
+                }
                 let trade = Self::from_route(
                     Route::new(
                         next_pools,
@@ -805,47 +1249,349 @@ where
                 )?;
                 sorted_insert(best_trades, trade, max_num_results, trade_comparator);
             } else if max_hops > 1 && pools.len() > 1 {
-                let pools_excluding_this_pool = pools[..i]
+                let pools_excluding_this_pool: Vec<Pool<TP>> = pools[..i]
                     .iter()
                     .chain(pools[i + 1..].iter())
                     .cloned()
                     .collect();
                 // otherwise, consider all the other paths that arrive at this token as long as we
                 // have not exceeded maxHops
-                let mut next_pools = vec![pool.clone()];
-                next_pools.extend(current_pools.clone());
-                Self::best_trade_exact_out(
-                    pools_excluding_this_pool,
+                Self::best_trade_exact_out_recursive(
+                    &pools_excluding_this_pool,
                     currency_in,
                     currency_amount_out,
-                    BestTradeOptions {
-                        max_num_results: Some(max_num_results),
-                        max_hops: Some(max_hops - 1),
-                    },
-                    next_pools,
-                    Some(&amount_in),
+                    max_num_results,
+                    max_hops - 1,
+                    max_input,
+                    &next_pools,
+                    &amount_in,
                     best_trades,
                 )?;
             }
         }
-        Ok(best_trades)
+        Ok(())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tests::*;
-    use once_cell::sync::Lazy;
+impl<TCurrency, TP> Trade<TCurrency, TCurrency, TP>
+where
+    TCurrency: BaseCurrency,
+    TP: Clone + TickDataProvider,
+{
+    /// Searches the pools for a cyclic route from `start_currency` back to itself whose output
+    /// exceeds the input, i.e. an arbitrage, making at most `max_hops` hops
+    ///
+    /// Routes are found with [`Trade::best_trade_exact_in`], so each pool is used at most once per
+    /// route and the most profitable cycle is returned.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pools`: The pools to consider in finding the cycle
+    /// * `start_currency`: The currency the cycle starts and ends in
+    /// * `amount_in`: The exact amount of `start_currency` to spend
+    /// * `max_hops`: The maximum number of hops of the cycle
+    ///
+    /// returns: The most profitable cyclic trade, or `None` if no cycle returns more than
+    /// `amount_in`
+    #[inline]
+    pub fn find_arbitrage(
+        pools: &[Pool<TP>],
+        start_currency: &TCurrency,
+        amount_in: &CurrencyAmount<TCurrency>,
+        max_hops: usize,
+    ) -> Result<Option<Self>, Error> {
+        assert!(amount_in.currency.equals(start_currency), "START_CURRENCY");
+        let best_trade = Self::best_trade_exact_in(
+            pools,
+            amount_in,
+            start_currency,
+            BestTradeOptions {
+                max_num_results: Some(1),
+                max_hops: Some(max_hops),
+                ..Default::default()
+            },
+        )?
+        .into_iter()
+        .next();
+        match best_trade {
+            Some(trade) if trade.output_amount()?.quotient() > amount_in.quotient() => {
+                Ok(Some(trade))
+            }
+            _ => Ok(None),
+        }
+    }
+}
 
-    fn v2_style_pool(
-        reserve0: CurrencyAmount<Currency>,
-        reserve1: CurrencyAmount<Currency>,
-        fee_amount: Option<FeeAmount>,
-    ) -> Pool<TickListDataProvider> {
-        let fee_amount = fee_amount.unwrap_or(FeeAmount::MEDIUM);
-        let sqrt_ratio_x96 = encode_sqrt_ratio_x96(reserve1.quotient(), reserve0.quotient());
-        let liquidity = (reserve0.quotient() * reserve1.quotient())
+#[cfg(feature = "parallel")]
+impl<TInput, TOutput, TP> Trade<TInput, TOutput, TP>
+where
+    TInput: BaseCurrency + Sync,
+    TOutput: BaseCurrency + Sync,
+    TP: Clone + TickDataProvider,
+    Pool<TP>: Sync,
+    Self: Send,
+{
+    /// Like [`Trade::best_trade_exact_in`], but explores the paths through each pool of the first
+    /// hop in parallel, for large pool sets with in-memory tick data
+    ///
+    /// The results of the branches are merged in pool order, so they are identical to those of
+    /// [`Trade::best_trade_exact_in`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `pools`: The pools to consider in finding the best trade
+    /// * `currency_amount_in`: The exact amount of input currency to spend
+    /// * `currency_out`: The desired currency out
+    /// * `best_trade_options`: Maximum number of results to return and maximum number of hops a
+    ///   returned trade can make, e.g. 1 hop goes through a single pool
+    #[inline]
+    pub fn best_trade_exact_in_parallel(
+        pools: &[Pool<TP>],
+        currency_amount_in: &CurrencyAmount<TInput>,
+        currency_out: &TOutput,
+        best_trade_options: BestTradeOptions,
+    ) -> Result<Vec<Self>, Error> {
+        assert!(!pools.is_empty(), "POOLS");
+        let max_num_results = best_trade_options.max_num_results.unwrap_or(3);
+        let max_hops = best_trade_options.max_hops.unwrap_or(3);
+        assert!(max_hops > 0, "MAX_HOPS");
+        let amount_in = to_currency_amount(currency_amount_in)?;
+        let branches = (0..pools.len())
+            .into_par_iter()
+            .map(|i| {
+                let mut best_trades = Vec::new();
+                Self::best_trade_exact_in_branch(
+                    pools,
+                    i,
+                    currency_amount_in,
+                    currency_out,
+                    max_num_results,
+                    max_hops,
+                    best_trade_options.min_output,
+                    &[],
+                    &amount_in,
+                    &mut best_trades,
+                )?;
+                Ok(best_trades)
+            })
+            .collect::<Result<Vec<Vec<Self>>, Error>>()?;
+        let mut best_trades = Vec::new();
+        for trade in branches.into_iter().flatten() {
+            sorted_insert(&mut best_trades, trade, max_num_results, trade_comparator);
+        }
+        Ok(best_trades)
+    }
+}
+
+/// Simulates a swap through the pool, consulting and populating the cache if given
+///
+/// ## Arguments
+///
+/// * `pool`: The pool to swap through
+/// * `amount`: The input amount of an exact input swap, or the output amount of an exact output
+///   swap
+/// * `exact_input`: Whether `amount` is the input amount
+/// * `cache`: The swap results to consult and populate
+fn cached_swap<TP: Clone + TickDataProvider>(
+    pool: &Pool<TP>,
+    amount: &CurrencyAmount<Currency>,
+    exact_input: bool,
+    cache: Option<&mut SwapCache>,
+) -> Result<CurrencyAmount<Currency>, Error> {
+    let swap = |amount: &CurrencyAmount<Currency>| -> Result<CurrencyAmount<Currency>, Error> {
+        let (amount, _) = if exact_input {
+            pool.get_output_amount(amount, None)?
+        } else {
+            pool.get_input_amount(amount, None)?
+        };
+        Ok(amount)
+    };
+    let Some(cache) = cache else {
+        return swap(amount);
+    };
+    let zero_for_one = amount.currency.equals(&pool.currency0) == exact_input;
+    let amount_specified = if exact_input {
+        I256::from_big_int(amount.quotient())
+    } else {
+        I256::from_big_int(-amount.quotient())
+    };
+    let key = (pool.pool_key.clone(), zero_for_one, amount_specified);
+    if let Some(amount) = cache.get(&key) {
+        return Ok(amount.clone());
+    }
+    let result = swap(amount)?;
+    cache.insert(key, result.clone());
+    Ok(result)
+}
+
+/// Returns true if one currency is native and the other is its wrapped token
+fn mixes_native_and_wrapped(a: &impl BaseCurrency, b: &impl BaseCurrency) -> bool {
+    a.is_native() != b.is_native() && a.wrapped().equals(b.wrapped())
+}
+
+/// Converts a currency amount of any currency type into a [`CurrencyAmount<Currency>`], the amount
+/// type threaded through the best trade search
+fn to_currency_amount(
+    amount: &CurrencyAmount<impl BaseCurrency>,
+) -> Result<CurrencyAmount<Currency>, Error> {
+    let currency = if amount.currency.is_native() {
+        Currency::NativeCurrency(Ether::on_chain(amount.currency.chain_id()))
+    } else {
+        Currency::Token(amount.currency.wrapped().clone())
+    };
+    CurrencyAmount::from_fractional_amount(
+        currency,
+        amount.numerator.clone(),
+        amount.denominator.clone(),
+    )
+    .map_err(Error::Core)
+}
+
+/// JSON representation of a currency for front-end consumption, identified by its v4 address,
+/// i.e. [`Address::ZERO`](alloy_primitives::Address::ZERO) for the native currency
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CurrencyJson {
+    address: String,
+    symbol: Option<String>,
+    decimals: u8,
+}
+
+#[cfg(feature = "serde")]
+impl CurrencyJson {
+    fn new(currency: &impl BaseCurrency) -> Self {
+        Self {
+            address: crate::prelude::to_address(currency).to_checksum(None),
+            symbol: currency.symbol().map(Into::into),
+            decimals: currency.decimals(),
+        }
+    }
+}
+
+/// JSON representation of a currency amount, with both its exact decimal value and its raw value
+/// in the smallest unit of the currency
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CurrencyAmountJson {
+    currency: CurrencyJson,
+    amount: String,
+    raw_amount: String,
+}
+
+#[cfg(feature = "serde")]
+impl CurrencyAmountJson {
+    fn new(amount: &CurrencyAmount<impl BaseCurrency>) -> Self {
+        Self {
+            currency: CurrencyJson::new(&amount.currency),
+            amount: amount.to_exact(),
+            raw_amount: format!("{}", amount.quotient()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SwapJson {
+    input_amount: CurrencyAmountJson,
+    output_amount: CurrencyAmountJson,
+    /// The currencies along the route, from input to output
+    route: Vec<CurrencyJson>,
+}
+
+#[cfg(feature = "serde")]
+impl SwapJson {
+    fn new<TInput, TOutput, TP>(swap: &Swap<TInput, TOutput, TP>) -> Self
+    where
+        TInput: BaseCurrency,
+        TOutput: BaseCurrency,
+        TP: TickDataProvider,
+    {
+        Self {
+            input_amount: CurrencyAmountJson::new(&swap.input_amount),
+            output_amount: CurrencyAmountJson::new(&swap.output_amount),
+            route: swap
+                .route
+                .currency_path()
+                .iter()
+                .map(CurrencyJson::new)
+                .collect(),
+        }
+    }
+}
+
+/// Serializes the swap for front-end consumption, omitting the pools' tick data
+#[cfg(feature = "serde")]
+impl<TInput, TOutput, TP> serde::Serialize for Swap<TInput, TOutput, TP>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: TickDataProvider,
+{
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&SwapJson::new(self), serializer)
+    }
+}
+
+/// Serializes the trade for front-end consumption, e.g.
+///
+/// ```json
+/// {
+///   "tradeType": "exactIn",
+///   "inputAmount": {
+///     "currency": { "address": "0x...", "symbol": "USDC", "decimals": 6 },
+///     "amount": "100",
+///     "rawAmount": "100000000"
+///   },
+///   "outputAmount": { ... },
+///   "swaps": [{ "inputAmount": { ... }, "outputAmount": { ... }, "route": [{ ... }, { ... }] }]
+/// }
+/// ```
+///
+/// The pools' tick data is omitted.
+#[cfg(feature = "serde")]
+impl<TInput, TOutput, TP> serde::Serialize for Trade<TInput, TOutput, TP>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: TickDataProvider,
+{
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{Error as _, SerializeStruct};
+
+        let trade_type = match self.trade_type {
+            TradeType::ExactInput => "exactIn",
+            TradeType::ExactOutput => "exactOut",
+        };
+        let input_amount = self.input_amount().map_err(S::Error::custom)?;
+        let output_amount = self.output_amount().map_err(S::Error::custom)?;
+        let mut state = serializer.serialize_struct("Trade", 4)?;
+        state.serialize_field("tradeType", trade_type)?;
+        state.serialize_field("inputAmount", &CurrencyAmountJson::new(&input_amount))?;
+        state.serialize_field("outputAmount", &CurrencyAmountJson::new(&output_amount))?;
+        state.serialize_field("swaps", &self.swaps)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use once_cell::sync::Lazy;
+
+    fn v2_style_pool(
+        reserve0: CurrencyAmount<Currency>,
+        reserve1: CurrencyAmount<Currency>,
+        fee_amount: Option<FeeAmount>,
+    ) -> Pool<TickListDataProvider> {
+        let fee_amount = fee_amount.unwrap_or(FeeAmount::MEDIUM);
+        let sqrt_ratio_x96 = encode_sqrt_ratio_x96(reserve1.quotient(), reserve0.quotient());
+        let liquidity = (reserve0.quotient() * reserve1.quotient())
             .sqrt()
             .to_u128()
             .unwrap();
@@ -1012,154 +1758,923 @@ mod tests {
                 TradeType::ExactOutput,
             )
             .unwrap();
-            assert_eq!(trade.input_currency().clone(), ETHER.clone());
-            assert_eq!(trade.output_currency().clone(), TOKEN0.clone());
+            assert_eq!(trade.input_currency().clone(), ETHER.clone());
+            assert_eq!(trade.output_currency().clone(), TOKEN0.clone());
+        }
+
+        #[test]
+        fn can_be_constructed_with_ether_as_output() {
+            let trade = Trade::from_route(
+                Route::new(vec![POOL_ETH_0.clone()], TOKEN0.clone(), ETHER.clone()).unwrap(),
+                CurrencyAmount::from_raw_amount(ETHER.clone(), 10000).unwrap(),
+                TradeType::ExactOutput,
+            )
+            .unwrap();
+            assert_eq!(trade.input_currency().clone(), TOKEN0.clone());
+            assert_eq!(trade.output_currency().clone(), ETHER.clone());
+        }
+
+        #[test]
+        fn can_be_constructed_with_ether_as_output_for_exact_input() {
+            let trade = Trade::from_route(
+                Route::new(vec![POOL_ETH_0.clone()], TOKEN0.clone(), ETHER.clone()).unwrap(),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            assert_eq!(trade.input_currency().clone(), TOKEN0.clone());
+            assert_eq!(trade.output_currency().clone(), ETHER.clone());
+        }
+    }
+
+    mod from_route_with_cache {
+        use super::*;
+        use alloc::sync::Arc;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use uniswap_v3_sdk::error::Error as V3Error;
+
+        /// Counts the tick lookups made by swap simulations
+        #[derive(Clone, Debug)]
+        struct CountingTickDataProvider {
+            inner: TickListDataProvider,
+            lookups: Arc<AtomicUsize>,
+        }
+
+        impl TickDataProvider for CountingTickDataProvider {
+            type Index = i32;
+
+            fn get_tick(&self, tick: i32) -> Result<&Tick<i32>, V3Error> {
+                self.lookups.fetch_add(1, Ordering::Relaxed);
+                self.inner.get_tick(tick)
+            }
+
+            fn next_initialized_tick_within_one_word(
+                &self,
+                tick: i32,
+                lte: bool,
+                tick_spacing: i32,
+            ) -> Result<(i32, bool), V3Error> {
+                self.lookups.fetch_add(1, Ordering::Relaxed);
+                self.inner
+                    .next_initialized_tick_within_one_word(tick, lte, tick_spacing)
+            }
+        }
+
+        fn counting_pool(
+            pool: &Pool<TickListDataProvider>,
+            lookups: &Arc<AtomicUsize>,
+        ) -> Pool<CountingTickDataProvider> {
+            Pool::new_with_tick_data_provider(
+                pool.currency0.clone(),
+                pool.currency1.clone(),
+                pool.fee,
+                pool.tick_spacing,
+                pool.hooks,
+                pool.sqrt_price_x96,
+                pool.liquidity,
+                CountingTickDataProvider {
+                    inner: pool.tick_data_provider.clone(),
+                    lookups: lookups.clone(),
+                },
+            )
+            .unwrap()
+        }
+
+        fn route(lookups: &Arc<AtomicUsize>) -> Route<Token, Token, CountingTickDataProvider> {
+            Route::new(
+                vec![
+                    counting_pool(&POOL_0_1, lookups),
+                    counting_pool(&POOL_1_2, lookups),
+                ],
+                TOKEN0.clone(),
+                TOKEN2.clone(),
+            )
+            .unwrap()
+        }
+
+        #[test]
+        fn populates_and_reuses_the_cache() {
+            for trade_type in [TradeType::ExactInput, TradeType::ExactOutput] {
+                let lookups = Arc::new(AtomicUsize::new(0));
+                let amount = match trade_type {
+                    TradeType::ExactInput => {
+                        CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap()
+                    }
+                    TradeType::ExactOutput => {
+                        CurrencyAmount::from_raw_amount(TOKEN2.clone(), 10000).unwrap()
+                    }
+                };
+                let expected =
+                    Trade::from_route(route(&lookups), amount.clone(), trade_type).unwrap();
+
+                let mut cache = SwapCache::default();
+                let first = Trade::from_route_with_cache(
+                    route(&lookups),
+                    amount.clone(),
+                    trade_type,
+                    &mut cache,
+                )
+                .unwrap();
+                assert_eq!(cache.len(), 2);
+                let lookups_after_first = lookups.load(Ordering::Relaxed);
+                assert!(lookups_after_first > 0);
+
+                let second =
+                    Trade::from_route_with_cache(route(&lookups), amount, trade_type, &mut cache)
+                        .unwrap();
+                assert_eq!(lookups.load(Ordering::Relaxed), lookups_after_first);
+                assert_eq!(cache.len(), 2);
+
+                for trade in [first, second] {
+                    assert_eq!(
+                        trade.input_amount().unwrap(),
+                        expected.input_amount().unwrap()
+                    );
+                    assert_eq!(
+                        trade.output_amount().unwrap(),
+                        expected.output_amount().unwrap()
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn does_not_reuse_results_for_other_amounts() {
+            let lookups = Arc::new(AtomicUsize::new(0));
+            let mut cache = SwapCache::default();
+            for amount in [10000, 20000] {
+                let trade = Trade::from_route_with_cache(
+                    route(&lookups),
+                    CurrencyAmount::from_raw_amount(TOKEN0.clone(), amount).unwrap(),
+                    TradeType::ExactInput,
+                    &mut cache,
+                )
+                .unwrap();
+                let expected = Trade::from_route(
+                    route(&lookups),
+                    CurrencyAmount::from_raw_amount(TOKEN0.clone(), amount).unwrap(),
+                    TradeType::ExactInput,
+                )
+                .unwrap();
+                assert_eq!(
+                    trade.output_amount().unwrap(),
+                    expected.output_amount().unwrap()
+                );
+            }
+            assert_eq!(cache.len(), 4);
+        }
+    }
+
+    mod from_route_auto {
+        use super::*;
+
+        #[test]
+        fn infers_exact_input_from_input_amount() {
+            let route = Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()).unwrap();
+            let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap();
+            let trade = Trade::from_route_auto(route.clone(), amount.clone()).unwrap();
+            let expected = Trade::exact_in(route, amount).unwrap();
+            assert_eq!(trade.trade_type, TradeType::ExactInput);
+            assert_eq!(
+                trade.input_amount().unwrap().quotient(),
+                expected.input_amount().unwrap().quotient()
+            );
+            assert_eq!(
+                trade.output_amount().unwrap().quotient(),
+                expected.output_amount().unwrap().quotient()
+            );
+        }
+
+        #[test]
+        fn infers_exact_output_from_output_amount() {
+            let route = Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()).unwrap();
+            let amount = CurrencyAmount::from_raw_amount(TOKEN1.clone(), 10000).unwrap();
+            let trade = Trade::from_route_auto(route.clone(), amount.clone()).unwrap();
+            let expected = Trade::exact_out(route, amount).unwrap();
+            assert_eq!(trade.trade_type, TradeType::ExactOutput);
+            assert_eq!(
+                trade.input_amount().unwrap().quotient(),
+                expected.input_amount().unwrap().quotient()
+            );
+            assert_eq!(
+                trade.output_amount().unwrap().quotient(),
+                expected.output_amount().unwrap().quotient()
+            );
+        }
+
+        #[test]
+        fn throws_if_amount_currency_not_in_route() {
+            let route = Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()).unwrap();
+            let amount = CurrencyAmount::from_raw_amount(TOKEN2.clone(), 10000).unwrap();
+            assert!(matches!(
+                Trade::from_route_auto(route, amount),
+                Err(Error::AmountCurrencyNotInRoute)
+            ));
+        }
+    }
+
+    mod with_slippage {
+        use super::*;
+
+        #[test]
+        fn exact_in_returns_minimum_amount_out() {
+            let slippage_tolerance = Percent::new(5, 100);
+            let (trade, minimum_amount_out) = Trade::exact_in_with_slippage(
+                Route::new(
+                    vec![POOL_0_1.clone(), POOL_1_2.clone()],
+                    TOKEN0.clone(),
+                    TOKEN2.clone(),
+                )
+                .unwrap(),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
+                slippage_tolerance.clone(),
+            )
+            .unwrap();
+            assert_eq!(trade.trade_type, TradeType::ExactInput);
+            assert_eq!(
+                minimum_amount_out,
+                trade.minimum_amount_out(slippage_tolerance, None).unwrap()
+            );
+        }
+
+        #[test]
+        fn exact_out_returns_maximum_amount_in() {
+            let slippage_tolerance = Percent::new(5, 100);
+            let (trade, maximum_amount_in) = Trade::exact_out_with_slippage(
+                Route::new(
+                    vec![POOL_0_1.clone(), POOL_1_2.clone()],
+                    TOKEN0.clone(),
+                    TOKEN2.clone(),
+                )
+                .unwrap(),
+                CurrencyAmount::from_raw_amount(TOKEN2.clone(), 10000).unwrap(),
+                slippage_tolerance.clone(),
+            )
+            .unwrap();
+            assert_eq!(trade.trade_type, TradeType::ExactOutput);
+            assert_eq!(
+                maximum_amount_in,
+                trade.maximum_amount_in(slippage_tolerance, None).unwrap()
+            );
+        }
+    }
+
+    mod fee_on_transfer {
+        use super::*;
+
+        fn route() -> Route<Token, Token, TickListDataProvider> {
+            Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()).unwrap()
+        }
+
+        #[test]
+        fn is_unsupported_for_exact_output() {
+            let trade = Trade::exact_out(
+                route(),
+                CurrencyAmount::from_raw_amount(TOKEN1.clone(), 100).unwrap(),
+            )
+            .unwrap();
+            assert!(matches!(
+                trade.with_fee_on_transfer_support(Percent::new(1, 100)),
+                Err(Error::FeeOnTransferExactOutUnsupported)
+            ));
+        }
+
+        #[test]
+        fn relaxes_minimum_amount_out_for_exact_input() {
+            let trade = Trade::exact_in(
+                route(),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
+            )
+            .unwrap();
+            assert!(!trade.supports_fee_on_transfer());
+            let slippage_tolerance = Percent::new(5, 100);
+            let output_amount = trade.output_amount().unwrap();
+            let minimum_amount_out = trade
+                .minimum_amount_out(slippage_tolerance.clone(), None)
+                .unwrap();
+            assert_eq!(
+                minimum_amount_out,
+                output_amount.multiply(&Fraction::new(100, 105)).unwrap()
+            );
+
+            let transfer_tax = Percent::new(3, 100);
+            let mut trade = trade
+                .with_fee_on_transfer_support(transfer_tax.clone())
+                .unwrap();
+            assert!(trade.supports_fee_on_transfer());
+            assert_eq!(trade.fee_on_transfer_tax(), Some(&transfer_tax));
+            // the slippage-based minimum is kept and only reduced by the transfer tax
+            let expected = minimum_amount_out
+                .multiply(&Fraction::new(97, 100))
+                .unwrap();
+            assert!(expected.quotient() > BigInt::from(0));
+            assert_eq!(
+                trade
+                    .minimum_amount_out(slippage_tolerance.clone(), None)
+                    .unwrap(),
+                expected
+            );
+            assert_eq!(
+                trade
+                    .minimum_amount_out_cached(slippage_tolerance, None)
+                    .unwrap(),
+                expected
+            );
+        }
+    }
+
+    mod minimum_amount_out {
+        use super::*;
+
+        #[test]
+        fn exact_in_rounds_down() {
+            let trade = Trade::exact_in(
+                Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()).unwrap(),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap(),
+            )
+            .unwrap();
+            let amount_out = CurrencyAmount::from_raw_amount(TOKEN1.clone(), 100).unwrap();
+            // 100 / 1.07 = 93.45..., which must not round up to 94
+            let minimum_amount_out = trade
+                .minimum_amount_out(Percent::new(7, 100), Some(amount_out))
+                .unwrap();
+            assert_eq!(minimum_amount_out.quotient(), BigInt::from(93));
+        }
+
+        #[test]
+        fn exact_in_never_exceeds_the_exact_minimum() {
+            let mut trade = Trade::exact_in(
+                Route::new(
+                    vec![POOL_0_1.clone(), POOL_1_2.clone()],
+                    TOKEN0.clone(),
+                    TOKEN2.clone(),
+                )
+                .unwrap(),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
+            )
+            .unwrap();
+            let output_amount = trade.output_amount().unwrap().quotient();
+            for bips in [1, 3, 7, 33, 50, 999] {
+                let slippage_tolerance = Percent::new(bips, 10000);
+                let minimum_amount_out = trade
+                    .minimum_amount_out(slippage_tolerance.clone(), None)
+                    .unwrap()
+                    .quotient();
+                assert_eq!(
+                    minimum_amount_out,
+                    trade
+                        .minimum_amount_out_cached(slippage_tolerance, None)
+                        .unwrap()
+                        .quotient()
+                );
+                // minimum * (1 + slippage) <= output < (minimum + 1) * (1 + slippage)
+                assert!(
+                    minimum_amount_out.clone() * (10000 + bips) <= output_amount.clone() * 10000
+                );
+                assert!((minimum_amount_out + 1) * (10000 + bips) > output_amount.clone() * 10000);
+            }
+        }
+    }
+
+    mod from_routes {
+        use super::*;
+
+        #[test]
+        fn can_be_constructed_with_ether_as_input_with_multiple_routes() {
+            let trade = Trade::from_routes(
+                vec![(
+                    CurrencyAmount::from_raw_amount(ETHER.clone(), 10000).unwrap(),
+                    Route::new(vec![POOL_ETH_0.clone()], ETHER.clone(), TOKEN0.clone()).unwrap(),
+                )],
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            assert_eq!(trade.input_currency().clone(), ETHER.clone());
+            assert_eq!(trade.output_currency().clone(), TOKEN0.clone());
+        }
+
+        #[test]
+        fn can_be_constructed_with_ether_as_input_for_exact_output_with_multiple_routes() {
+            let trade = Trade::from_routes(
+                vec![
+                    (
+                        CurrencyAmount::from_raw_amount(TOKEN0.clone(), 3000).unwrap(),
+                        Route::new(vec![POOL_ETH_0.clone()], ETHER.clone(), TOKEN0.clone())
+                            .unwrap(),
+                    ),
+                    (
+                        CurrencyAmount::from_raw_amount(TOKEN0.clone(), 7000).unwrap(),
+                        Route::new(
+                            vec![POOL_ETH_1.clone(), POOL_0_1.clone()],
+                            ETHER.clone(),
+                            TOKEN0.clone(),
+                        )
+                        .unwrap(),
+                    ),
+                ],
+                TradeType::ExactOutput,
+            )
+            .unwrap();
+            assert_eq!(trade.input_currency().clone(), ETHER.clone());
+            assert_eq!(trade.output_currency().clone(), TOKEN0.clone());
+        }
+
+        #[test]
+        fn can_be_constructed_with_ether_as_output_with_multiple_routes() {
+            let trade = Trade::from_routes(
+                vec![
+                    (
+                        CurrencyAmount::from_raw_amount(ETHER.clone(), 4000).unwrap(),
+                        Route::new(vec![POOL_ETH_0.clone()], TOKEN0.clone(), ETHER.clone())
+                            .unwrap(),
+                    ),
+                    (
+                        CurrencyAmount::from_raw_amount(ETHER.clone(), 6000).unwrap(),
+                        Route::new(
+                            vec![POOL_0_1.clone(), POOL_ETH_1.clone()],
+                            TOKEN0.clone(),
+                            ETHER.clone(),
+                        )
+                        .unwrap(),
+                    ),
+                ],
+                TradeType::ExactOutput,
+            )
+            .unwrap();
+            assert_eq!(trade.input_currency().clone(), TOKEN0.clone());
+            assert_eq!(trade.output_currency().clone(), ETHER.clone());
+        }
+
+        #[test]
+        fn can_be_constructed_with_ether_as_output_for_exact_input_with_multiple_routes() {
+            let trade = Trade::from_routes(
+                vec![
+                    (
+                        CurrencyAmount::from_raw_amount(TOKEN0.clone(), 3000).unwrap(),
+                        Route::new(vec![POOL_ETH_0.clone()], TOKEN0.clone(), ETHER.clone())
+                            .unwrap(),
+                    ),
+                    (
+                        CurrencyAmount::from_raw_amount(TOKEN0.clone(), 7000).unwrap(),
+                        Route::new(
+                            vec![POOL_0_1.clone(), POOL_ETH_1.clone()],
+                            TOKEN0.clone(),
+                            ETHER.clone(),
+                        )
+                        .unwrap(),
+                    ),
+                ],
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            assert_eq!(trade.input_currency().clone(), TOKEN0.clone());
+            assert_eq!(trade.output_currency().clone(), ETHER.clone());
+        }
+
+        #[test]
+        #[should_panic(expected = "POOLS_DUPLICATED")]
+        fn throws_if_pools_are_reused_between_routes() {
+            let _ = Trade::from_routes(
+                vec![
+                    (
+                        CurrencyAmount::from_raw_amount(TOKEN0.clone(), 4500).unwrap(),
+                        Route::new(
+                            vec![POOL_0_1.clone(), POOL_ETH_1.clone()],
+                            TOKEN0.clone(),
+                            ETHER.clone(),
+                        )
+                        .unwrap(),
+                    ),
+                    (
+                        CurrencyAmount::from_raw_amount(TOKEN0.clone(), 5500).unwrap(),
+                        Route::new(
+                            vec![POOL_0_1.clone(), POOL_1_2.clone(), POOL_ETH_2.clone()],
+                            TOKEN0.clone(),
+                            ETHER.clone(),
+                        )
+                        .unwrap(),
+                    ),
+                ],
+                TradeType::ExactInput,
+            );
+        }
+    }
+
+    mod from_swaps {
+        use super::*;
+
+        #[test]
+        fn throws_for_empty_swaps() {
+            let result: Result<Trade<Token, Token, TickListDataProvider>, Error> =
+                Trade::from_swaps(
+                    Vec::<(CurrencyAmount<Token>, _)>::new(),
+                    TradeType::ExactInput,
+                );
+            assert!(matches!(result, Err(Error::EmptyTrade)));
+        }
+
+        #[test]
+        fn simulates_two_routes() {
+            let swaps = vec![
+                (
+                    CurrencyAmount::from_raw_amount(TOKEN0.clone(), 3000).unwrap(),
+                    Route::new(vec![POOL_0_2.clone()], TOKEN0.clone(), TOKEN2.clone()).unwrap(),
+                ),
+                (
+                    CurrencyAmount::from_raw_amount(TOKEN0.clone(), 7000).unwrap(),
+                    Route::new(
+                        vec![POOL_0_1.clone(), POOL_1_2.clone()],
+                        TOKEN0.clone(),
+                        TOKEN2.clone(),
+                    )
+                    .unwrap(),
+                ),
+            ];
+            let trade = Trade::from_swaps(swaps.clone(), TradeType::ExactInput).unwrap();
+            assert_eq!(trade.swaps.len(), 2);
+            assert_eq!(
+                trade.input_amount().unwrap(),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap()
+            );
+            for (swap, (amount, route)) in trade.swaps.iter().zip(swaps) {
+                assert_eq!(swap.input_amount, amount);
+                assert_eq!(
+                    swap.output_amount,
+                    Trade::exact_in(route, amount)
+                        .unwrap()
+                        .output_amount()
+                        .unwrap()
+                );
+            }
+        }
+    }
+
+    mod best_trade_exact_in {
+        use super::*;
+
+        #[test]
+        #[should_panic(expected = "POOLS")]
+        fn throws_with_empty_pools() {
+            let _ = Trade::<Token, Token, TickListDataProvider>::best_trade_exact_in(
+                &[],
+                &CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
+                &TOKEN2.clone(),
+                BestTradeOptions::default(),
+            );
+        }
+
+        #[test]
+        fn provides_best_route() {
+            let result = Trade::best_trade_exact_in(
+                &[POOL_0_2.clone(), POOL_0_1.clone(), POOL_1_2.clone()],
+                &CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
+                &TOKEN2.clone(),
+                BestTradeOptions::default(),
+            )
+            .unwrap();
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0].swaps[0].route.pools, vec![POOL_0_2.clone()]);
+            assert_eq!(
+                result[0].input_amount().unwrap().quotient(),
+                BigInt::from(10000)
+            );
+            assert_eq!(
+                result[0].output_amount().unwrap().quotient(),
+                BigInt::from(9971)
+            );
+            assert_eq!(
+                result[1].swaps[0].route.pools,
+                vec![POOL_0_1.clone(), POOL_1_2.clone()]
+            );
+            assert_eq!(
+                result[1].input_amount().unwrap().quotient(),
+                BigInt::from(10000)
+            );
+            assert_eq!(
+                result[1].output_amount().unwrap().quotient(),
+                BigInt::from(7004)
+            );
+        }
+
+        #[test]
+        fn two_hop_route_matches_from_route() {
+            let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap();
+            let result = Trade::best_trade_exact_in(
+                &[POOL_0_1.clone(), POOL_1_2.clone()],
+                &amount_in,
+                &TOKEN2.clone(),
+                BestTradeOptions::default(),
+            )
+            .unwrap();
+            assert_eq!(result.len(), 1);
+            let expected = Trade::from_route(
+                Route::new(
+                    vec![POOL_0_1.clone(), POOL_1_2.clone()],
+                    TOKEN0.clone(),
+                    TOKEN2.clone(),
+                )
+                .unwrap(),
+                amount_in,
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            assert_eq!(result[0], expected);
+        }
+
+        #[test]
+        fn respects_max_hops() {
+            let result = Trade::best_trade_exact_in(
+                &[POOL_0_2.clone(), POOL_0_1.clone(), POOL_1_2.clone()],
+                &CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10).unwrap(),
+                &TOKEN2.clone(),
+                BestTradeOptions {
+                    max_hops: Some(1),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].swaps[0].route.pools, vec![POOL_0_2.clone()]);
+        }
+
+        #[test]
+        fn respects_max_num_results() {
+            let result = Trade::best_trade_exact_in(
+                &[POOL_0_2.clone(), POOL_0_1.clone(), POOL_1_2.clone()],
+                &CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10).unwrap(),
+                &TOKEN2.clone(),
+                BestTradeOptions {
+                    max_num_results: Some(1),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            assert_eq!(result.len(), 1);
+        }
+
+        #[test]
+        fn excludes_trades_below_min_output() {
+            let result = Trade::best_trade_exact_in(
+                &[POOL_0_2.clone(), POOL_0_1.clone(), POOL_1_2.clone()],
+                &CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
+                &TOKEN2.clone(),
+                BestTradeOptions {
+                    min_output: Some(9000),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].swaps[0].route.pools, vec![POOL_0_2.clone()]);
+            assert_eq!(
+                result[0].output_amount().unwrap().quotient(),
+                BigInt::from(9971)
+            );
+        }
+
+        #[test]
+        fn works_for_ether_currency_input() {
+            let result = Trade::best_trade_exact_in(
+                &[
+                    POOL_ETH_0.clone(),
+                    POOL_0_1.clone(),
+                    POOL_0_3.clone(),
+                    POOL_1_3.clone(),
+                ],
+                &CurrencyAmount::from_raw_amount(ETHER.clone(), 100).unwrap(),
+                &TOKEN3.clone(),
+                BestTradeOptions::default(),
+            )
+            .unwrap();
+            assert_eq!(result.len(), 2);
+            for trade in &result {
+                assert_eq!(trade.input_currency().clone(), ETHER.clone());
+                assert_eq!(trade.output_currency().clone(), TOKEN3.clone());
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    mod best_trade_exact_in_parallel {
+        use super::*;
+
+        #[test]
+        fn matches_sequential_results() {
+            let pools = [
+                POOL_0_1.clone(),
+                POOL_0_2.clone(),
+                POOL_0_3.clone(),
+                POOL_1_2.clone(),
+                POOL_1_3.clone(),
+            ];
+            let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap();
+            for max_hops in 1..=3 {
+                let options = BestTradeOptions {
+                    max_num_results: Some(10),
+                    max_hops: Some(max_hops),
+                    ..Default::default()
+                };
+                let sequential =
+                    Trade::best_trade_exact_in(&pools, &amount_in, &TOKEN3.clone(), options)
+                        .unwrap();
+                let parallel = Trade::best_trade_exact_in_parallel(
+                    &pools,
+                    &amount_in,
+                    &TOKEN3.clone(),
+                    options,
+                )
+                .unwrap();
+                assert!(!parallel.is_empty());
+                assert_eq!(parallel.len(), sequential.len());
+                for (a, b) in parallel.iter().zip(&sequential) {
+                    assert_eq!(a.swaps[0].route.pools, b.swaps[0].route.pools);
+                    assert_eq!(a.output_amount().unwrap(), b.output_amount().unwrap());
+                }
+            }
+        }
+    }
+
+    mod best_trade_exact_out {
+        use super::*;
+
+        #[test]
+        fn provides_best_route() {
+            let result = Trade::best_trade_exact_out(
+                &[POOL_0_2.clone(), POOL_0_1.clone(), POOL_1_2.clone()],
+                &TOKEN0.clone(),
+                &CurrencyAmount::from_raw_amount(TOKEN2.clone(), 10000).unwrap(),
+                BestTradeOptions::default(),
+            )
+            .unwrap();
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0].swaps[0].route.pools, vec![POOL_0_2.clone()]);
+            assert_eq!(
+                result[0].input_amount().unwrap().quotient(),
+                BigInt::from(10032)
+            );
+            assert_eq!(
+                result[0].output_amount().unwrap().quotient(),
+                BigInt::from(10000)
+            );
+            assert_eq!(
+                result[1].swaps[0].route.pools,
+                vec![POOL_0_1.clone(), POOL_1_2.clone()]
+            );
+            assert_eq!(
+                result[1].input_amount().unwrap().quotient(),
+                BigInt::from(15488)
+            );
+            assert_eq!(
+                result[1].output_amount().unwrap().quotient(),
+                BigInt::from(10000)
+            );
         }
 
         #[test]
-        fn can_be_constructed_with_ether_as_output() {
-            let trade = Trade::from_route(
-                Route::new(vec![POOL_ETH_0.clone()], TOKEN0.clone(), ETHER.clone()).unwrap(),
-                CurrencyAmount::from_raw_amount(ETHER.clone(), 10000).unwrap(),
-                TradeType::ExactOutput,
+        fn excludes_trades_above_max_input() {
+            let result = Trade::best_trade_exact_out(
+                &[POOL_0_2.clone(), POOL_0_1.clone(), POOL_1_2.clone()],
+                &TOKEN0.clone(),
+                &CurrencyAmount::from_raw_amount(TOKEN2.clone(), 10000).unwrap(),
+                BestTradeOptions {
+                    max_input: Some(11000),
+                    ..Default::default()
+                },
             )
             .unwrap();
-            assert_eq!(trade.input_currency().clone(), TOKEN0.clone());
-            assert_eq!(trade.output_currency().clone(), ETHER.clone());
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].swaps[0].route.pools, vec![POOL_0_2.clone()]);
+            assert_eq!(
+                result[0].input_amount().unwrap().quotient(),
+                BigInt::from(10032)
+            );
         }
 
         #[test]
-        fn can_be_constructed_with_ether_as_output_for_exact_input() {
-            let trade = Trade::from_route(
-                Route::new(vec![POOL_ETH_0.clone()], TOKEN0.clone(), ETHER.clone()).unwrap(),
-                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
-                TradeType::ExactInput,
+        fn works_for_ether_currency_output() {
+            let result = Trade::best_trade_exact_out(
+                &[
+                    POOL_ETH_0.clone(),
+                    POOL_0_1.clone(),
+                    POOL_0_3.clone(),
+                    POOL_1_3.clone(),
+                ],
+                &TOKEN3.clone(),
+                &CurrencyAmount::from_raw_amount(ETHER.clone(), 100).unwrap(),
+                BestTradeOptions::default(),
             )
             .unwrap();
-            assert_eq!(trade.input_currency().clone(), TOKEN0.clone());
-            assert_eq!(trade.output_currency().clone(), ETHER.clone());
+            assert_eq!(result.len(), 2);
+            for trade in &result {
+                assert_eq!(trade.input_currency().clone(), TOKEN3.clone());
+                assert_eq!(trade.output_currency().clone(), ETHER.clone());
+            }
         }
     }
 
-    mod from_routes {
+    mod find_arbitrage {
         use super::*;
 
         #[test]
-        fn can_be_constructed_with_ether_as_input_with_multiple_routes() {
-            let trade = Trade::from_routes(
-                vec![(
-                    CurrencyAmount::from_raw_amount(ETHER.clone(), 10000).unwrap(),
-                    Route::new(vec![POOL_ETH_0.clone()], ETHER.clone(), TOKEN0.clone()).unwrap(),
-                )],
-                TradeType::ExactInput,
+        fn finds_cycle_through_mispriced_pools() {
+            let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap();
+            let trade = Trade::find_arbitrage(
+                &[POOL_0_1.clone(), POOL_0_2.clone(), POOL_1_2.clone()],
+                &TOKEN0.clone(),
+                &amount_in,
+                3,
             )
+            .unwrap()
             .unwrap();
-            assert_eq!(trade.input_currency().clone(), ETHER.clone());
-            assert_eq!(trade.output_currency().clone(), TOKEN0.clone());
+            assert_eq!(
+                trade.swaps[0].route.pools,
+                vec![POOL_0_2.clone(), POOL_1_2.clone(), POOL_0_1.clone()]
+            );
+            assert!(trade.input_currency().equals(&TOKEN0.clone()));
+            assert!(trade.output_currency().equals(&TOKEN0.clone()));
+            assert!(trade.output_amount().unwrap().quotient() > amount_in.quotient());
         }
 
         #[test]
-        fn can_be_constructed_with_ether_as_input_for_exact_output_with_multiple_routes() {
-            let trade = Trade::from_routes(
-                vec![
-                    (
-                        CurrencyAmount::from_raw_amount(TOKEN0.clone(), 3000).unwrap(),
-                        Route::new(vec![POOL_ETH_0.clone()], ETHER.clone(), TOKEN0.clone())
-                            .unwrap(),
-                    ),
-                    (
-                        CurrencyAmount::from_raw_amount(TOKEN0.clone(), 7000).unwrap(),
-                        Route::new(
-                            vec![POOL_ETH_1.clone(), POOL_0_1.clone()],
-                            ETHER.clone(),
-                            TOKEN0.clone(),
-                        )
-                        .unwrap(),
-                    ),
-                ],
-                TradeType::ExactOutput,
+        fn finds_nothing_in_balanced_pools() {
+            let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap();
+            let trade = Trade::find_arbitrage(
+                &[POOL_0_1.clone(), POOL_ETH_0.clone(), POOL_ETH_1.clone()],
+                &TOKEN0.clone(),
+                &amount_in,
+                3,
             )
             .unwrap();
-            assert_eq!(trade.input_currency().clone(), ETHER.clone());
-            assert_eq!(trade.output_currency().clone(), TOKEN0.clone());
+            assert!(trade.is_none());
         }
 
         #[test]
-        fn can_be_constructed_with_ether_as_output_with_multiple_routes() {
-            let trade = Trade::from_routes(
-                vec![
-                    (
-                        CurrencyAmount::from_raw_amount(ETHER.clone(), 4000).unwrap(),
-                        Route::new(vec![POOL_ETH_0.clone()], TOKEN0.clone(), ETHER.clone())
-                            .unwrap(),
-                    ),
-                    (
-                        CurrencyAmount::from_raw_amount(ETHER.clone(), 6000).unwrap(),
-                        Route::new(
-                            vec![POOL_0_1.clone(), POOL_ETH_1.clone()],
-                            TOKEN0.clone(),
-                            ETHER.clone(),
-                        )
-                        .unwrap(),
-                    ),
-                ],
-                TradeType::ExactOutput,
+        fn needs_enough_hops_to_close_the_cycle() {
+            let amount_in = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap();
+            let trade = Trade::find_arbitrage(
+                &[POOL_0_1.clone(), POOL_0_2.clone(), POOL_1_2.clone()],
+                &TOKEN0.clone(),
+                &amount_in,
+                2,
             )
             .unwrap();
-            assert_eq!(trade.input_currency().clone(), TOKEN0.clone());
-            assert_eq!(trade.output_currency().clone(), ETHER.clone());
+            assert!(trade.is_none());
         }
+    }
+
+    mod from_quote {
+        use super::*;
 
         #[test]
-        fn can_be_constructed_with_ether_as_output_for_exact_input_with_multiple_routes() {
-            let trade = Trade::from_routes(
-                vec![
-                    (
-                        CurrencyAmount::from_raw_amount(TOKEN0.clone(), 3000).unwrap(),
-                        Route::new(vec![POOL_ETH_0.clone()], TOKEN0.clone(), ETHER.clone())
-                            .unwrap(),
-                    ),
-                    (
-                        CurrencyAmount::from_raw_amount(TOKEN0.clone(), 7000).unwrap(),
-                        Route::new(
-                            vec![POOL_0_1.clone(), POOL_ETH_1.clone()],
-                            TOKEN0.clone(),
-                            ETHER.clone(),
-                        )
-                        .unwrap(),
-                    ),
-                ],
+        fn uses_quoted_output_of_exact_input() {
+            let route = Route::new(
+                vec![POOL_0_1.clone(), POOL_1_2.clone()],
+                TOKEN0.clone(),
+                TOKEN2.clone(),
+            )
+            .unwrap();
+            let trade = Trade::from_quote(
+                route,
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap(),
+                U256::from(69),
                 TradeType::ExactInput,
             )
             .unwrap();
-            assert_eq!(trade.input_currency().clone(), TOKEN0.clone());
-            assert_eq!(trade.output_currency().clone(), ETHER.clone());
+            assert_eq!(trade.trade_type, TradeType::ExactInput);
+            assert_eq!(trade.input_amount().unwrap().quotient(), BigInt::from(100));
+            assert_eq!(trade.output_amount().unwrap().quotient(), BigInt::from(69));
+            assert!(trade.output_currency().equals(&TOKEN2.clone()));
         }
 
         #[test]
-        #[should_panic(expected = "POOLS_DUPLICATED")]
-        fn throws_if_pools_are_reused_between_routes() {
-            let _ = Trade::from_routes(
-                vec![
-                    (
-                        CurrencyAmount::from_raw_amount(TOKEN0.clone(), 4500).unwrap(),
-                        Route::new(
-                            vec![POOL_0_1.clone(), POOL_ETH_1.clone()],
-                            TOKEN0.clone(),
-                            ETHER.clone(),
-                        )
-                        .unwrap(),
-                    ),
-                    (
-                        CurrencyAmount::from_raw_amount(TOKEN0.clone(), 5500).unwrap(),
-                        Route::new(
-                            vec![POOL_0_1.clone(), POOL_1_2.clone(), POOL_ETH_2.clone()],
-                            TOKEN0.clone(),
-                            ETHER.clone(),
-                        )
-                        .unwrap(),
-                    ),
-                ],
+        fn uses_quoted_input_of_exact_output() {
+            let route = Route::new(
+                vec![POOL_0_1.clone(), POOL_1_2.clone()],
+                TOKEN0.clone(),
+                TOKEN2.clone(),
+            )
+            .unwrap();
+            let trade = Trade::from_quote(
+                route,
+                CurrencyAmount::from_raw_amount(TOKEN2.clone(), 100).unwrap(),
+                U256::from(156),
+                TradeType::ExactOutput,
+            )
+            .unwrap();
+            assert_eq!(trade.trade_type, TradeType::ExactOutput);
+            assert_eq!(trade.input_amount().unwrap().quotient(), BigInt::from(156));
+            assert_eq!(trade.output_amount().unwrap().quotient(), BigInt::from(100));
+            assert!(trade.input_currency().equals(&TOKEN0.clone()));
+        }
+
+        #[test]
+        #[should_panic(expected = "INPUT")]
+        fn exact_input_amount_must_be_in_input_currency() {
+            let route = Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()).unwrap();
+            let _ = Trade::from_quote(
+                route,
+                CurrencyAmount::from_raw_amount(TOKEN1.clone(), 100).unwrap(),
+                U256::from(99),
                 TradeType::ExactInput,
             );
         }
@@ -1311,6 +2826,134 @@ mod tests {
         }
     }
 
+    mod empty_trade {
+        use super::*;
+
+        #[test]
+        fn cannot_be_constructed_without_swaps() {
+            let result: Result<Trade<Token, Token, TickListDataProvider>, Error> =
+                Trade::create_unchecked_trade_with_multiple_routes(vec![], TradeType::ExactInput);
+            assert!(matches!(result, Err(Error::EmptyTrade)));
+        }
+
+        #[test]
+        fn amounts_return_error_without_swaps() {
+            let mut trade = Trade::create_unchecked_trade(
+                Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()).unwrap(),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 10000).unwrap(),
+                CurrencyAmount::from_raw_amount(TOKEN1.clone(), 10000).unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            trade.swaps.clear();
+            assert!(matches!(trade.input_amount(), Err(Error::EmptyTrade)));
+            assert!(matches!(trade.output_amount(), Err(Error::EmptyTrade)));
+        }
+    }
+
+    mod summary {
+        use super::*;
+
+        #[test]
+        fn summarizes_exact_input_trade() {
+            let pool = v2_style_pool(
+                CurrencyAmount::from_raw_amount(
+                    DAI.clone().into(),
+                    1_000_000 * BigInt::from(ONE_ETHER),
+                )
+                .unwrap(),
+                CurrencyAmount::from_raw_amount(USDC.clone().into(), 1_000_000_000_000_u64)
+                    .unwrap(),
+                Some(FeeAmount::LOWEST),
+            );
+            let trade = Trade::create_unchecked_trade(
+                Route::new(vec![pool], USDC.clone(), DAI.clone()).unwrap(),
+                CurrencyAmount::from_raw_amount(USDC.clone(), 100_000_000).unwrap(),
+                CurrencyAmount::from_raw_amount(DAI.clone(), 995 * BigInt::from(ONE_ETHER) / 10)
+                    .unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            assert_eq!(
+                trade.summary().unwrap(),
+                "exactIn 100 USDC -> 99.5 DAI via 1 pool, impact 0.5%"
+            );
+        }
+    }
+
+    mod value_in {
+        use super::*;
+        use uniswap_sdk_core::token;
+
+        #[test]
+        fn converts_output_to_reference_currency() {
+            let pool = v2_style_pool(
+                CurrencyAmount::from_raw_amount(
+                    DAI.clone().into(),
+                    1_000_000 * BigInt::from(ONE_ETHER),
+                )
+                .unwrap(),
+                CurrencyAmount::from_raw_amount(USDC.clone().into(), 1_000_000_000_000_u64)
+                    .unwrap(),
+                Some(FeeAmount::LOWEST),
+            );
+            let trade = Trade::create_unchecked_trade(
+                Route::new(vec![pool], USDC.clone(), DAI.clone()).unwrap(),
+                CurrencyAmount::from_raw_amount(USDC.clone(), 100_000_000).unwrap(),
+                CurrencyAmount::from_raw_amount(DAI.clone(), 995 * BigInt::from(ONE_ETHER) / 10)
+                    .unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            let usd: Currency =
+                token!(1, "00000000000000000000000000000000000000ff", 18, "USD").into();
+            let value = trade
+                .value_in(&Price::new(DAI.clone(), usd.clone(), 1, 1))
+                .unwrap();
+            assert!(value.currency.equals(&usd));
+            assert_eq!(value.to_exact(), "99.5");
+        }
+    }
+
+    mod inconsistent_native_usage {
+        use super::*;
+
+        fn swap(
+            pool: &Pool<TickListDataProvider>,
+            input: Currency,
+        ) -> Swap<Currency, Token, TickListDataProvider> {
+            Swap::new(
+                Route::new(vec![pool.clone()], input.clone(), TOKEN0.clone()).unwrap(),
+                CurrencyAmount::from_raw_amount(input, 100).unwrap(),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 69).unwrap(),
+            )
+        }
+
+        #[test]
+        fn rejects_native_and_wrapped_inputs() {
+            let result = Trade::create_unchecked_trade_with_multiple_routes(
+                vec![
+                    swap(&POOL_ETH_0, ETHER.clone().into()),
+                    swap(&POOL_WETH_0, WETH.clone().into()),
+                ],
+                TradeType::ExactInput,
+            );
+            assert!(matches!(result, Err(Error::InconsistentNativeUsage)));
+        }
+
+        #[test]
+        fn accepts_consistent_inputs() {
+            let result = Trade::create_unchecked_trade_with_multiple_routes(
+                vec![
+                    swap(&POOL_ETH_0, ETHER.clone().into()),
+                    swap(&POOL_WETH_0, ETHER.clone().into()),
+                ],
+                TradeType::ExactInput,
+            );
+            assert!(result.is_ok());
+        }
+    }
+
     mod route_and_swaps {
         use super::*;
 
@@ -1356,6 +2999,27 @@ mod tests {
             .unwrap()
         });
 
+        #[test]
+        fn swap_execution_price_is_output_over_input() {
+            let swaps = &MULTI_ROUTE.swaps;
+            assert_eq!(
+                swaps[0].execution_price(),
+                Price::new(TOKEN0.clone(), TOKEN2.clone(), 50, 35)
+            );
+            assert_eq!(
+                swaps[1].execution_price(),
+                Price::new(TOKEN0.clone(), TOKEN2.clone(), 50, 34)
+            );
+            assert_eq!(
+                swaps[0]
+                    .execution_price()
+                    .quote(&swaps[0].input_amount)
+                    .unwrap()
+                    .quotient(),
+                swaps[0].output_amount.quotient()
+            );
+        }
+
         #[test]
         fn can_access_routes_for_both_single_and_multi_route_trades() {
             assert_eq!(MULTI_ROUTE.swaps.len(), 2);
@@ -1454,6 +3118,29 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn per_route_worst_prices_are_no_better_than_route_prices() {
+                let worst_prices = EXACT_IN_MULTI_ROUTE
+                    .per_route_worst_prices(Percent::new(5, 100))
+                    .unwrap();
+                assert_eq!(worst_prices.len(), 2);
+                for (swap, worst_price) in EXACT_IN_MULTI_ROUTE.swaps.iter().zip(&worst_prices) {
+                    let execution_price = Price::from_currency_amounts(
+                        swap.input_amount.clone(),
+                        swap.output_amount.clone(),
+                    );
+                    assert!(worst_price.as_fraction() < execution_price.as_fraction());
+                }
+                assert_eq!(
+                    worst_prices[0],
+                    Price::new(TOKEN0.clone(), TOKEN2.clone(), 5250, 3500)
+                );
+                assert_eq!(
+                    worst_prices[1],
+                    Price::new(TOKEN0.clone(), TOKEN2.clone(), 5250, 3400)
+                );
+            }
+
             #[test]
             fn returns_exact_if_nonzero_with_multiple_routes() {
                 assert_eq!(
@@ -1675,6 +3362,30 @@ mod tests {
             }
         }
 
+        #[test]
+        fn is_near_zero_for_small_trade_on_mixed_decimal_pool() {
+            let pool = v2_style_pool(
+                CurrencyAmount::from_raw_amount(
+                    DAI.clone().into(),
+                    1_000_000 * BigInt::from(ONE_ETHER),
+                )
+                .unwrap(),
+                CurrencyAmount::from_raw_amount(USDC.clone().into(), 1_000_000_000_000_u64)
+                    .unwrap(),
+                Some(FeeAmount::LOWEST),
+            );
+            let route = Route::new(vec![pool], USDC.clone(), DAI.clone()).unwrap();
+            assert_eq!(route.mid_price().unwrap().to_fixed(4, None), "1.0000");
+            let trade = Trade::exact_in(
+                route,
+                CurrencyAmount::from_raw_amount(USDC.clone(), 1_000_000).unwrap(),
+            )
+            .unwrap();
+            let price_impact = trade.price_impact().unwrap();
+            assert!(price_impact >= Percent::default());
+            assert!(price_impact < Percent::new(2, 10000));
+        }
+
         mod exact_output {
             use super::*;
 
@@ -1763,4 +3474,67 @@ mod tests {
             }
         }
     }
+
+    #[cfg(feature = "serde")]
+    mod serialize {
+        use super::*;
+
+        #[test]
+        fn serializes_currencies_amounts_and_route() {
+            let trade = Trade::from_route(
+                Route::new(
+                    vec![POOL_ETH_0.clone(), POOL_0_1.clone()],
+                    ETHER.clone(),
+                    TOKEN1.clone(),
+                )
+                .unwrap(),
+                CurrencyAmount::from_raw_amount(ETHER.clone(), 10000).unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            let json = serde_json::to_value(&trade).unwrap();
+
+            assert_eq!(json["tradeType"], "exactIn");
+            let input_amount = &json["inputAmount"];
+            assert_eq!(
+                input_amount["currency"]["address"],
+                Address::ZERO.to_checksum(None)
+            );
+            assert_eq!(input_amount["currency"]["symbol"], "ETH");
+            assert_eq!(input_amount["currency"]["decimals"], 18);
+            assert_eq!(input_amount["rawAmount"], "10000");
+            assert_eq!(
+                input_amount["amount"],
+                trade.input_amount().unwrap().to_exact()
+            );
+            let output_amount = &json["outputAmount"];
+            assert_eq!(
+                output_amount["currency"]["address"],
+                TOKEN1.address.to_checksum(None)
+            );
+            assert_eq!(
+                output_amount["rawAmount"],
+                format!("{}", trade.output_amount().unwrap().quotient())
+            );
+
+            let swaps = json["swaps"].as_array().unwrap();
+            assert_eq!(swaps.len(), 1);
+            assert_eq!(swaps[0]["inputAmount"], json["inputAmount"]);
+            assert_eq!(swaps[0]["outputAmount"], json["outputAmount"]);
+            let route: Vec<_> = swaps[0]["route"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|currency| currency["address"].as_str().unwrap())
+                .collect();
+            assert_eq!(
+                route,
+                [
+                    Address::ZERO.to_checksum(None),
+                    TOKEN0.address.to_checksum(None),
+                    TOKEN1.address.to_checksum(None),
+                ]
+            );
+        }
+    }
 }
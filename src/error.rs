@@ -36,4 +36,86 @@ pub enum Error {
 
     #[error("Insufficient liquidity")]
     InsufficientLiquidity,
+
+    /// Thrown when a reserved sentinel address, such as [`MSG_SENDER`], is passed as an explicit
+    /// recipient.
+    #[error("Reserved address {0} cannot be used as a recipient")]
+    ReservedAddress(alloy_primitives::Address),
+
+    /// Thrown when a trade is constructed or evaluated without any swaps.
+    #[error("Trade has no swaps")]
+    EmptyTrade,
+
+    /// Thrown when [`V4Planner::try_finalize`] is called before any actions are queued.
+    #[error("Planner has no actions")]
+    EmptyPlanner,
+
+    /// Thrown when a position tick is not a multiple of the pool's tick spacing.
+    #[error("Tick {tick} is not a multiple of the tick spacing")]
+    TickNotUsable { tick: i32 },
+
+    /// Thrown when the params of an action are not a valid encoding for its opcode.
+    #[error("Malformed params for action {opcode}")]
+    MalformedActionParams { opcode: u8 },
+
+    /// Thrown when the routes of a trade mix the native and wrapped forms of the same currency.
+    /// Normalize all routes to either the native currency or its wrapped token.
+    #[error("Routes mix native and wrapped forms of the same currency")]
+    InconsistentNativeUsage,
+
+    /// Thrown when the currency of an amount is neither the input nor the output of a route.
+    #[error("Amount currency is neither the input nor the output of the route")]
+    AmountCurrencyNotInRoute,
+
+    /// Thrown when a pool's tick spacing is outside of [`MIN_TICK_SPACING`] and
+    /// [`MAX_TICK_SPACING`].
+    #[error("Invalid tick spacing {tick_spacing}")]
+    InvalidTickSpacing { tick_spacing: i32 },
+
+    /// Thrown when fee-on-transfer support is requested for an exact output trade.
+    #[error("Exact output trades do not support fee-on-transfer tokens")]
+    FeeOnTransferExactOutUnsupported,
+
+    /// Thrown when a route has no pools to encode.
+    #[error("Route has no pools")]
+    EmptyRoute,
+
+    /// Thrown when a hook address has a combination of permission flags, or a fee, that the pool
+    /// manager rejects.
+    #[error("Invalid hook address {0}")]
+    InvalidHookAddress(alloy_primitives::Address),
+
+    /// Thrown when a value does not fit in the `uint160` of a sqrt price.
+    #[error("Sqrt price overflows uint160")]
+    SqrtPriceOverflow,
+
+    /// Thrown when the deadline of a call is not after the current block timestamp.
+    #[error("Deadline {deadline} has passed at {now}")]
+    DeadlinePassed {
+        deadline: alloy_primitives::U256,
+        now: alloy_primitives::U256,
+    },
+
+    /// Thrown when the liquidity of a position exceeds the maximum liquidity per tick of its pool.
+    #[error("Liquidity {liquidity} exceeds the maximum of {max_liquidity} per tick")]
+    LiquidityOverflow {
+        liquidity: u128,
+        max_liquidity: u128,
+    },
+
+    /// Thrown when a tick is outside of `MIN_TICK` and `MAX_TICK`.
+    #[error("Tick {tick} is out of range")]
+    TickOutOfRange { tick: i32 },
+
+    /// Thrown when the lower tick of a position is not below its upper tick.
+    #[error("Tick lower {tick_lower} is not below tick upper {tick_upper}")]
+    InvalidTickOrder { tick_lower: i32, tick_upper: i32 },
+
+    /// Thrown when a percentage is not within 0% and 100%.
+    #[error("Percentage is not within 0% and 100%")]
+    PercentageOutOfRange,
+
+    /// Thrown when a price is given with a zero amount on either side.
+    #[error("Price amounts must be non-zero")]
+    ZeroPriceAmount,
 }
@@ -1,6 +1,6 @@
 use crate::prelude::{Error, *};
 use alloc::vec::Vec;
-use alloy_primitives::{address, Address, Bytes, PrimitiveSignature, U160, U256};
+use alloy_primitives::{address, uint, Address, Bytes, PrimitiveSignature, U160, U256};
 use alloy_sol_types::{eip712_domain, SolCall};
 use derive_more::{Deref, DerefMut};
 use uniswap_sdk_core::prelude::*;
@@ -11,9 +11,23 @@ use uniswap_v3_sdk::prelude::{
 pub use uniswap_v3_sdk::prelude::NFTPermitData;
 
 /// Shared Action Constants used in the v4 Router and v4 position manager
+///
+/// [`MSG_SENDER`] is a sentinel resolved by the contracts to the caller of the transaction. It is
+/// not a real recipient, so it cannot be passed explicitly as the recipient of a mint; pass the
+/// zero address instead to mint to the caller.
 pub const MSG_SENDER: Address = address!("0000000000000000000000000000000000000001");
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Amount sentinel resolved by the contracts to the full open delta of a currency, i.e. settle or
+/// take exactly what is owed.
+pub const OPEN_DELTA: U256 = U256::ZERO;
+
+/// Amount sentinel resolved by the contracts to their entire balance of a currency, used to settle
+/// with everything the router or position manager currently holds, e.g. after a wrap or a prior
+/// take.
+pub const CONTRACT_BALANCE: U256 =
+    uint!(0x8000000000000000000000000000000000000000000000000000000000000000_U256);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct CommonOptions {
     /// How much the pool price is allowed to move from the specified action.
     pub slippage_tolerance: Percent,
@@ -29,19 +43,26 @@ pub struct ModifyPositionSpecificOptions {
     pub token_id: U256,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MintSpecificOptions {
-    /// The account that should receive the minted NFT.
+    /// The account that should receive the minted NFT. The zero address mints to the caller,
+    /// i.e. [`MSG_SENDER`].
     pub recipient: Address,
     /// Creates pool if not initialized before mint.
     pub create_pool: bool,
     /// Initial price to set on the pool if creating.
     pub sqrt_price_x96: Option<U160>,
+    /// Leaves `initializePool` out of the calldata when creating the pool, so that it can be
+    /// sent in its own transaction, e.g. via [`add_call_parameters_split`].
+    pub separate_initialize: bool,
     /// Whether the mint is part of a migration from V3 to V4.
     pub migrate: bool,
+    /// Optional data to pass to hooks for the `MINT_POSITION` action only, overriding
+    /// [`CommonOptions::hook_data`].
+    pub mint_hook_data: Option<Bytes>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AddLiquiditySpecificOptions {
     Mint(MintSpecificOptions),
     Increase(ModifyPositionSpecificOptions),
@@ -57,6 +78,8 @@ pub struct AddLiquidityOptions {
     pub use_native: Option<Ether>,
     /// The optional permit2 batch permit parameters for spending token0 and token1.
     pub batch_permit: Option<BatchPermitOptions>,
+    /// The optional permit2 single permit parameters for spending one of the tokens.
+    pub single_permit: Option<SinglePermitOptions>,
     /// [`MintSpecificOptions`] or [`IncreaseSpecificOptions`]
     pub specific_opts: AddLiquiditySpecificOptions,
 }
@@ -76,6 +99,72 @@ pub struct RemoveLiquidityOptions {
     /// The optional permit of the token ID being exited, in case the exit transaction is being
     /// sent by an account that does not own the NFT
     pub permit: Option<NFTPermitOptions>,
+    /// Whether to leave the deltas of the withdrawn currencies open so that the caller can consume
+    /// them in subsequent actions, instead of taking them with a `TAKE_PAIR`, by default false.
+    pub keep_deltas_open: bool,
+}
+
+impl Default for RemoveLiquidityOptions {
+    /// Exits the entire position without burning its NFT and takes the withdrawn currencies. The
+    /// default deadline of zero has already passed, so it must be set before use.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            common_opts: CommonOptions::default(),
+            token_id: U256::ZERO,
+            liquidity_percentage: Percent::new(1, 1),
+            burn_token: false,
+            permit: None,
+            keep_deltas_open: false,
+        }
+    }
+}
+
+impl RemoveLiquidityOptions {
+    /// Creates options exiting the entire position and burning its NFT, taking the withdrawn
+    /// currencies to the caller.
+    ///
+    /// ## Arguments
+    ///
+    /// * `token_id`: The ID of the position to exit
+    /// * `common_opts`: The common options of the call, including the deadline
+    #[inline]
+    #[must_use]
+    pub fn full_burn(token_id: U256, common_opts: CommonOptions) -> Self {
+        Self {
+            common_opts,
+            token_id,
+            liquidity_percentage: Percent::new(1, 1),
+            burn_token: true,
+            permit: None,
+            keep_deltas_open: false,
+        }
+    }
+
+    /// Creates options exiting a percentage of the position without burning its NFT, taking the
+    /// withdrawn currencies to the caller.
+    ///
+    /// ## Arguments
+    ///
+    /// * `token_id`: The ID of the position to exit
+    /// * `liquidity_percentage`: The percentage of position liquidity to exit
+    /// * `common_opts`: The common options of the call, including the deadline
+    #[inline]
+    #[must_use]
+    pub const fn partial(
+        token_id: U256,
+        liquidity_percentage: Percent,
+        common_opts: CommonOptions,
+    ) -> Self {
+        Self {
+            common_opts,
+            token_id,
+            liquidity_percentage,
+            burn_token: false,
+            permit: None,
+            keep_deltas_open: false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deref, DerefMut)]
@@ -85,10 +174,50 @@ pub struct CollectOptions {
     pub common_opts: CommonOptions,
     /// Indicates the ID of the position to collect for.
     pub token_id: U256,
-    /// The account that should receive the tokens.
+    /// The account that should receive the tokens. [`MSG_SENDER`] sends them to the caller.
     pub recipient: Address,
 }
 
+impl CollectOptions {
+    /// Creates options collecting to the given recipient.
+    ///
+    /// ## Arguments
+    ///
+    /// * `token_id`: The ID of the position to collect for
+    /// * `recipient`: The account that should receive the tokens
+    /// * `common_opts`: The common options of the call, including the deadline
+    #[inline]
+    #[must_use]
+    pub const fn new(token_id: U256, recipient: Address, common_opts: CommonOptions) -> Self {
+        Self {
+            common_opts,
+            token_id,
+            recipient,
+        }
+    }
+
+    /// Creates options collecting to the sender of the transaction.
+    ///
+    /// The recipient is set to [`MSG_SENDER`], which the position manager resolves to the caller,
+    /// not to the owner of the position. When an approved operator sends the transaction, the
+    /// fees go to the operator; use [`Self::new`] with the owner's address to collect to the owner
+    /// instead.
+    ///
+    /// ## Arguments
+    ///
+    /// * `token_id`: The ID of the position to collect for
+    /// * `common_opts`: The common options of the call
+    #[inline]
+    #[must_use]
+    pub const fn to_caller(token_id: U256, common_opts: CommonOptions) -> Self {
+        Self {
+            common_opts,
+            token_id,
+            recipient: MSG_SENDER,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct TransferOptions {
     /// The account sending the NFT.
@@ -110,6 +239,13 @@ pub struct BatchPermitOptions {
     pub signature: Bytes,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SinglePermitOptions {
+    pub owner: Address,
+    pub permit_single: AllowanceTransferPermitSingle,
+    pub signature: Bytes,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deref, DerefMut)]
 pub struct NFTPermitOptions {
     #[deref]
@@ -132,11 +268,14 @@ pub fn create_call_parameters(pool_key: PoolKey, sqrt_price_x96: U160) -> Method
 ///
 /// ## Notes
 ///
-/// - If the pool does not exist yet, the `initializePool` call is encoded.
+/// - If the pool does not exist yet, the `initializePool` call is encoded, unless
+///   `separate_initialize` is set.
 /// - If it is a mint, encode `MINT_POSITION`. If migrating, encode a `SETTLE` and `SWEEP` for both
 ///   currencies. Else, encode a `SETTLE_PAIR`. If on a NATIVE pool, encode a `SWEEP`.
 /// - Else, encode `INCREASE_LIQUIDITY` and `SETTLE_PAIR`. If it is on a NATIVE pool, encode a
 ///   `SWEEP`.
+/// - When the maximum amount of one currency is zero, e.g. for a single-sided position entirely
+///   out of range, a `SETTLE` of the other currency is encoded instead of the `SETTLE_PAIR`.
 ///
 /// ## Arguments
 ///
@@ -148,13 +287,16 @@ pub fn add_call_parameters<TP: TickDataProvider>(
     options: AddLiquidityOptions,
 ) -> Result<MethodParameters, Error> {
     assert!(position.liquidity > 0, "ZERO_LIQUIDITY");
+    if let AddLiquiditySpecificOptions::Mint(opts) = &options.specific_opts {
+        mint_recipient(opts.recipient)?;
+    }
 
-    let mut calldatas: Vec<Bytes> = Vec::with_capacity(3);
+    let mut calldatas: Vec<Bytes> = Vec::with_capacity(4);
     let mut planner = V4PositionPlanner::default();
 
     // Encode initialize pool.
-    if let AddLiquiditySpecificOptions::Mint(opts) = options.specific_opts {
-        if opts.create_pool {
+    if let AddLiquiditySpecificOptions::Mint(opts) = &options.specific_opts {
+        if opts.create_pool && !opts.separate_initialize {
             // No planner used here because initializePool is not supported as an Action
             calldatas.push(encode_initialize_pool(
                 position.pool.pool_key.clone(),
@@ -177,8 +319,15 @@ pub fn add_call_parameters<TP: TickDataProvider>(
             batch_permit.signature,
         ));
     }
+    if let Some(single_permit) = options.single_permit {
+        calldatas.push(encode_permit_single(
+            single_permit.owner,
+            single_permit.permit_single,
+            single_permit.signature,
+        ));
+    }
 
-    match options.specific_opts {
+    match &options.specific_opts {
         AddLiquiditySpecificOptions::Mint(opts) => {
             planner.add_mint(
                 &position.pool,
@@ -187,8 +336,10 @@ pub fn add_call_parameters<TP: TickDataProvider>(
                 U256::from(position.liquidity),
                 u128::try_from(amount0_max).unwrap(),
                 u128::try_from(amount1_max).unwrap(),
-                opts.recipient,
-                options.common_opts.hook_data,
+                mint_recipient(opts.recipient)?,
+                opts.mint_hook_data
+                    .clone()
+                    .unwrap_or(options.common_opts.hook_data),
             );
         }
         AddLiquiditySpecificOptions::Increase(opts) => {
@@ -203,19 +354,20 @@ pub fn add_call_parameters<TP: TickDataProvider>(
     }
 
     // If migrating, we need to settle and sweep both currencies individually
-    if let AddLiquiditySpecificOptions::Mint(opts) = options.specific_opts {
+    if let AddLiquiditySpecificOptions::Mint(opts) = &options.specific_opts {
         if opts.migrate {
             // payer is v4 positiion manager
             planner.add_settle(&position.pool.currency0, false, None);
             planner.add_settle(&position.pool.currency1, false, None);
-            planner.add_sweep(&position.pool.currency0, opts.recipient);
-            planner.add_sweep(&position.pool.currency1, opts.recipient);
+            let recipient = mint_recipient(opts.recipient)?;
+            planner.add_sweep(&position.pool.currency0, recipient);
+            planner.add_sweep(&position.pool.currency1, recipient);
         } else {
             // need to settle both currencies when minting / adding liquidity (user is the payer)
-            planner.add_settle_pair(&position.pool.currency0, &position.pool.currency1);
+            add_settle_owed(&mut planner, &position.pool, amount0_max, amount1_max);
         }
     } else {
-        planner.add_settle_pair(&position.pool.currency0, &position.pool.currency1);
+        add_settle_owed(&mut planner, &position.pool, amount0_max, amount1_max);
     }
 
     // Any sweeping must happen after the settling.
@@ -245,12 +397,101 @@ pub fn add_call_parameters<TP: TickDataProvider>(
     })
 }
 
+/// Settles the currencies owed by the user for adding liquidity, skipping a currency whose maximum
+/// amount is zero since nothing can be owed in it
+#[inline]
+fn add_settle_owed<TP: TickDataProvider>(
+    planner: &mut V4PositionPlanner,
+    pool: &Pool<TP>,
+    amount0_max: U256,
+    amount1_max: U256,
+) {
+    match (amount0_max.is_zero(), amount1_max.is_zero()) {
+        (false, true) => {
+            planner.add_settle(&pool.currency0, true, None);
+        }
+        (true, false) => {
+            planner.add_settle(&pool.currency1, true, None);
+        }
+        _ => planner.add_settle_pair(&pool.currency0, &pool.currency1),
+    }
+}
+
+/// Encodes the method parameters for adding liquidity to a position, as one transaction per
+/// returned [`MethodParameters`].
+///
+/// If the position is minted with both `create_pool` and `separate_initialize` set, the
+/// `initializePool` call is returned first as its own transaction, followed by the calldata of
+/// [`add_call_parameters`]. Otherwise the result is the single call of [`add_call_parameters`].
+///
+/// ## Arguments
+///
+/// * `position`: The position to be added.
+/// * `options`: The options for adding liquidity.
+#[inline]
+pub fn add_call_parameters_split<TP: TickDataProvider>(
+    position: &mut Position<TP>,
+    options: AddLiquidityOptions,
+) -> Result<Vec<MethodParameters>, Error> {
+    let initialize = match &options.specific_opts {
+        AddLiquiditySpecificOptions::Mint(opts) if opts.create_pool && opts.separate_initialize => {
+            Some(create_call_parameters(
+                position.pool.pool_key.clone(),
+                opts.sqrt_price_x96.expect("NO_SQRT_PRICE"),
+            ))
+        }
+        _ => None,
+    };
+    let add = add_call_parameters(position, options)?;
+    Ok(initialize.into_iter().chain([add]).collect())
+}
+
+/// Produces the calldata like [`add_call_parameters`], but first rejects a deadline that has
+/// already passed, since the call would revert on-chain
+///
+/// ## Arguments
+///
+/// * `position`: The position to be added.
+/// * `options`: The options for adding liquidity.
+/// * `now`: The current block timestamp, or `None` to skip the deadline check
+///
+/// ## Errors
+///
+/// Returns [`Error::DeadlinePassed`] if the deadline is not after `now`.
+#[inline]
+pub fn add_call_parameters_checked<TP: TickDataProvider>(
+    position: &mut Position<TP>,
+    options: AddLiquidityOptions,
+    now: Option<U256>,
+) -> Result<MethodParameters, Error> {
+    let deadline = options.common_opts.deadline;
+    if let Some(now) = now {
+        if deadline <= now {
+            return Err(Error::DeadlinePassed { deadline, now });
+        }
+    }
+    add_call_parameters(position, options)
+}
+
+/// Resolves the recipient of a mint, mapping the zero address to [`MSG_SENDER`] and rejecting the
+/// sentinel as an explicit recipient
+fn mint_recipient(recipient: Address) -> Result<Address, Error> {
+    if recipient == MSG_SENDER {
+        Err(Error::ReservedAddress(recipient))
+    } else if recipient == Address::ZERO {
+        Ok(MSG_SENDER)
+    } else {
+        Ok(recipient)
+    }
+}
+
 /// Produces the calldata for completely or partially exiting a position
 ///
 /// ## Notes
 ///
 /// - If the liquidity percentage is 100%, encode `BURN_POSITION` and then `TAKE_PAIR`.
 /// - Else, encode `DECREASE_LIQUIDITY` and then `TAKE_PAIR`.
+/// - The `TAKE_PAIR` is omitted if `options.keep_deltas_open` is true.
 ///
 /// ## Arguments
 ///
@@ -296,23 +537,7 @@ pub fn remove_call_parameters<TP: TickDataProvider>(
         );
     } else {
         // construct a partial position with a percentage of liquidity
-        let partial_position = Position::new(
-            Pool::new(
-                position.pool.currency0.clone(),
-                position.pool.currency1.clone(),
-                position.pool.fee,
-                position.pool.tick_spacing.to_i24().as_i32(),
-                position.pool.hooks,
-                position.pool.sqrt_price_x96,
-                position.pool.liquidity,
-            )?,
-            (options.liquidity_percentage * Percent::new(position.liquidity, 1))
-                .quotient()
-                .to_u128()
-                .unwrap(),
-            position.tick_lower.try_into().unwrap(),
-            position.tick_upper.try_into().unwrap(),
-        );
+        let partial_position = position.fraction(&options.liquidity_percentage)?;
 
         // If the partial position has liquidity=0, this is a collect call and collectCallParameters
         // should be used
@@ -331,11 +556,13 @@ pub fn remove_call_parameters<TP: TickDataProvider>(
         );
     }
 
-    planner.add_take_pair(
-        &position.pool.currency0,
-        &position.pool.currency1,
-        MSG_SENDER,
-    );
+    if !options.keep_deltas_open {
+        planner.add_take_pair(
+            &position.pool.currency0,
+            &position.pool.currency1,
+            MSG_SENDER,
+        );
+    }
     calldatas.push(encode_modify_liquidities(
         planner.0.finalize(),
         options.common_opts.deadline,
@@ -383,8 +610,48 @@ pub fn collect_call_parameters<TP: TickDataProvider>(
     }
 }
 
+/// Encodes the `initializePool` call creating a pool on the position manager without minting any
+/// liquidity
+///
+/// ## Arguments
+///
+/// * `pool_key`: The key of the pool to initialize
+/// * `sqrt_price_x96`: The initial sqrt price of the pool as a Q64.96
+///
+/// ## Examples
+///
+/// ```
+/// use alloy_primitives::{address, aliases::U24, Address};
+/// use alloy_sol_types::SolCall;
+/// use uniswap_sdk_core::{prelude::*, token};
+/// use uniswap_v3_sdk::prelude::encode_sqrt_ratio_x96;
+/// use uniswap_v4_sdk::prelude::*;
+///
+/// let usdc = token!(
+///     1,
+///     "A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+///     6,
+///     "USDC",
+///     "USD Coin"
+/// );
+/// let pool_key = Pool::get_pool_key(
+///     &Currency::NativeCurrency(Ether::on_chain(1)),
+///     &Currency::Token(usdc),
+///     U24::from(500),
+///     10,
+///     Address::ZERO,
+/// )
+/// .unwrap();
+/// let sqrt_price_x96 = encode_sqrt_ratio_x96(1, 1);
+/// let calldata = encode_initialize_pool(pool_key.clone(), sqrt_price_x96);
+///
+/// let call = IPositionManager::initializePoolCall::abi_decode(&calldata, true).unwrap();
+/// assert_eq!(call.key, pool_key);
+/// assert_eq!(call.sqrtPriceX96, sqrt_price_x96);
+/// ```
 #[inline]
-fn encode_initialize_pool(pool_key: PoolKey, sqrt_price_x96: U160) -> Bytes {
+#[must_use]
+pub fn encode_initialize_pool(pool_key: PoolKey, sqrt_price_x96: U160) -> Bytes {
     IPositionManager::initializePoolCall {
         key: pool_key,
         sqrtPriceX96: sqrt_price_x96,
@@ -418,6 +685,21 @@ pub fn encode_permit_batch(
     .into()
 }
 
+#[inline]
+pub fn encode_permit_single(
+    owner: Address,
+    permit_single: AllowanceTransferPermitSingle,
+    signature: Bytes,
+) -> Bytes {
+    IPermit2Forwarder::permitCall {
+        owner,
+        permitSingle: permit_single,
+        signature,
+    }
+    .abi_encode()
+    .into()
+}
+
 #[inline]
 pub fn encode_erc721_permit(
     spender: Address,
@@ -501,3 +783,490 @@ pub const fn get_permit_data(
         values: permit,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::uint;
+    use alloy_sol_types::sol;
+    use once_cell::sync::Lazy;
+    use uniswap_v3_sdk::prelude::{encode_sqrt_ratio_x96, FeeAmount};
+
+    sol! {
+        function multicall(bytes[] data) external payable returns (bytes[] memory results);
+    }
+
+    static POOL_0_1: Lazy<Pool> = Lazy::new(|| {
+        Pool::new(
+            TOKEN0.clone().into(),
+            TOKEN1.clone().into(),
+            FeeAmount::MEDIUM.into(),
+            60,
+            Address::ZERO,
+            encode_sqrt_ratio_x96(1, 1),
+            0,
+        )
+        .unwrap()
+    });
+
+    const DEADLINE: U256 = uint!(123_U256);
+    const TOKEN_ID: U256 = uint!(1_U256);
+
+    fn common_opts() -> CommonOptions {
+        CommonOptions {
+            slippage_tolerance: Percent::new(1, 100),
+            deadline: DEADLINE,
+            hook_data: Bytes::default(),
+        }
+    }
+
+    /// Splits the calldata of a multicall into its calls, or returns the calldata as the only call
+    fn decode_calls(calldata: &Bytes) -> Vec<Bytes> {
+        multicallCall::abi_decode(calldata, true)
+            .map(|call| call.data)
+            .unwrap_or_else(|_| vec![calldata.clone()])
+    }
+
+    /// Returns the actions encoded in a `modifyLiquidities` call
+    fn parse_modify_liquidities(calldata: &Bytes) -> Vec<Actions> {
+        let call = IPositionManager::modifyLiquiditiesCall::abi_decode(calldata, true).unwrap();
+        assert_eq!(call.deadline, DEADLINE);
+        parse_calldata(&call.unlockData).unwrap().actions
+    }
+
+    /// Returns the action commands encoded in a `modifyLiquidities` call
+    fn modify_liquidities_commands(calldata: &Bytes) -> Vec<u8> {
+        parse_modify_liquidities(calldata)
+            .iter()
+            .map(Actions::command)
+            .collect()
+    }
+
+    mod add_call_parameters {
+        use super::*;
+
+        fn mint_options(recipient: Address) -> AddLiquidityOptions {
+            AddLiquidityOptions {
+                common_opts: common_opts(),
+                use_native: None,
+                batch_permit: None,
+                single_permit: None,
+                specific_opts: AddLiquiditySpecificOptions::Mint(MintSpecificOptions {
+                    recipient,
+                    create_pool: false,
+                    sqrt_price_x96: None,
+                    separate_initialize: false,
+                    migrate: false,
+                    mint_hook_data: None,
+                }),
+            }
+        }
+
+        fn mint_owner(calldata: &Bytes) -> Address {
+            let calls = decode_calls(calldata);
+            match &parse_modify_liquidities(calls.last().unwrap())[0] {
+                Actions::MINT_POSITION(params) => params.owner,
+                _ => panic!("expected MINT_POSITION"),
+            }
+        }
+
+        #[test]
+        fn mints_to_recipient() {
+            let recipient = address!("000000000000000000000000000000000000000a");
+            let mut position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            let MethodParameters { calldata, .. } =
+                add_call_parameters(&mut position, mint_options(recipient)).unwrap();
+            assert_eq!(mint_owner(&calldata), recipient);
+        }
+
+        #[test]
+        fn substitutes_msg_sender_for_zero_address() {
+            let mut position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            let MethodParameters { calldata, .. } =
+                add_call_parameters(&mut position, mint_options(Address::ZERO)).unwrap();
+            assert_eq!(mint_owner(&calldata), MSG_SENDER);
+        }
+
+        fn mint_hook_data(calldata: &Bytes) -> Bytes {
+            let calls = decode_calls(calldata);
+            match &parse_modify_liquidities(calls.last().unwrap())[0] {
+                Actions::MINT_POSITION(params) => params.hookData.clone(),
+                _ => panic!("expected MINT_POSITION"),
+            }
+        }
+
+        #[test]
+        fn mint_uses_common_hook_data_by_default() {
+            let mut position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            let mut options = mint_options(Address::ZERO);
+            options.hook_data = Bytes::from_static(&[0x01]);
+            let MethodParameters { calldata, .. } =
+                add_call_parameters(&mut position, options).unwrap();
+            assert_eq!(mint_hook_data(&calldata), Bytes::from_static(&[0x01]));
+        }
+
+        #[test]
+        fn mint_hook_data_overrides_common_hook_data() {
+            let mut position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            let mut options = mint_options(Address::ZERO);
+            options.hook_data = Bytes::from_static(&[0x01]);
+            if let AddLiquiditySpecificOptions::Mint(opts) = &mut options.specific_opts {
+                opts.mint_hook_data = Some(Bytes::from_static(&[0x02]));
+            }
+            let MethodParameters { calldata, .. } =
+                add_call_parameters(&mut position, options).unwrap();
+            assert_eq!(mint_hook_data(&calldata), Bytes::from_static(&[0x02]));
+            let calls = decode_calls(&calldata);
+            assert_eq!(
+                modify_liquidities_commands(calls.last().unwrap()),
+                vec![
+                    Actions::MINT_POSITION(Default::default()).command(),
+                    Actions::SETTLE_PAIR(Default::default()).command(),
+                ]
+            );
+        }
+
+        #[test]
+        fn settles_native_currency_as_zero_address() {
+            let pool = Pool::new(
+                ETHER.clone().into(),
+                TOKEN1.clone().into(),
+                FeeAmount::MEDIUM.into(),
+                60,
+                Address::ZERO,
+                encode_sqrt_ratio_x96(1, 1),
+                0,
+            )
+            .unwrap();
+            let mut position = Position::new(pool, ONE_ETHER, -60, 60);
+            let MethodParameters { calldata, value } = add_call_parameters(
+                &mut position,
+                AddLiquidityOptions {
+                    use_native: Some(ETHER.clone()),
+                    ..mint_options(Address::ZERO)
+                },
+            )
+            .unwrap();
+            assert!(value > U256::ZERO);
+            let calls = decode_calls(&calldata);
+            let actions = parse_modify_liquidities(calls.last().unwrap());
+            assert_eq!(
+                actions[1],
+                Actions::SETTLE_PAIR(SettlePairParams {
+                    currency0: Address::ZERO,
+                    currency1: TOKEN1.address,
+                })
+            );
+            assert_eq!(
+                actions[2],
+                Actions::SWEEP(SweepParams {
+                    currency: Address::ZERO,
+                    recipient: MSG_SENDER,
+                })
+            );
+        }
+
+        #[test]
+        fn settles_only_the_owed_currency_of_a_single_sided_position() {
+            // the position is entirely above the current price, so only currency0 is owed
+            let mut position = Position::new(POOL_0_1.clone(), ONE_ETHER, 600, 1200);
+            let MethodParameters { calldata, .. } =
+                add_call_parameters(&mut position, mint_options(Address::ZERO)).unwrap();
+            let calls = decode_calls(&calldata);
+            let actions = parse_modify_liquidities(calls.last().unwrap());
+            assert_eq!(
+                modify_liquidities_commands(calls.last().unwrap()),
+                vec![
+                    Actions::MINT_POSITION(Default::default()).command(),
+                    Actions::SETTLE(Default::default()).command(),
+                ]
+            );
+            assert_eq!(
+                actions[1],
+                Actions::SETTLE(SettleParams {
+                    currency: TOKEN0.address,
+                    amount: OPEN_DELTA,
+                    payerIsUser: true,
+                })
+            );
+        }
+
+        fn create_pool_options(separate_initialize: bool) -> AddLiquidityOptions {
+            let mut options = mint_options(Address::ZERO);
+            if let AddLiquiditySpecificOptions::Mint(opts) = &mut options.specific_opts {
+                opts.create_pool = true;
+                opts.sqrt_price_x96 = Some(POOL_0_1.sqrt_price_x96);
+                opts.separate_initialize = separate_initialize;
+            }
+            options
+        }
+
+        #[test]
+        fn bundles_initialize_by_default() {
+            let mut position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            let method_parameters =
+                add_call_parameters_split(&mut position, create_pool_options(false)).unwrap();
+            assert_eq!(method_parameters.len(), 1);
+            let calls = decode_calls(&method_parameters[0].calldata);
+            assert_eq!(calls.len(), 2);
+            assert_eq!(
+                calls[0][..4],
+                IPositionManager::initializePoolCall::SELECTOR
+            );
+        }
+
+        #[test]
+        fn separates_initialize_into_its_own_call() {
+            let mut position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            let method_parameters =
+                add_call_parameters_split(&mut position, create_pool_options(true)).unwrap();
+            assert_eq!(method_parameters.len(), 2);
+            let initialize =
+                create_call_parameters(POOL_0_1.pool_key.clone(), POOL_0_1.sqrt_price_x96);
+            assert_eq!(method_parameters[0].calldata, initialize.calldata);
+            assert_eq!(method_parameters[0].value, initialize.value);
+            let calls = decode_calls(&method_parameters[1].calldata);
+            assert_eq!(calls.len(), 1);
+            assert_eq!(
+                modify_liquidities_commands(&calls[0]),
+                vec![
+                    Actions::MINT_POSITION(Default::default()).command(),
+                    Actions::SETTLE_PAIR(Default::default()).command(),
+                ]
+            );
+            assert_eq!(
+                add_call_parameters(&mut position, create_pool_options(true))
+                    .unwrap()
+                    .calldata,
+                method_parameters[1].calldata
+            );
+        }
+
+        #[test]
+        fn checked_rejects_passed_deadline() {
+            let mut position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            for now in [DEADLINE, DEADLINE + U256::from(1)] {
+                let result = add_call_parameters_checked(
+                    &mut position,
+                    mint_options(Address::ZERO),
+                    Some(now),
+                );
+                let Err(Error::DeadlinePassed { deadline, now: at }) = result else {
+                    panic!("expected DeadlinePassed");
+                };
+                assert_eq!(deadline, DEADLINE);
+                assert_eq!(at, now);
+            }
+            let expected = add_call_parameters(&mut position, mint_options(Address::ZERO))
+                .unwrap()
+                .calldata;
+            for now in [None, Some(DEADLINE - U256::from(1))] {
+                assert_eq!(
+                    add_call_parameters_checked(&mut position, mint_options(Address::ZERO), now)
+                        .unwrap()
+                        .calldata,
+                    expected
+                );
+            }
+        }
+
+        #[test]
+        fn encodes_single_permit_before_modify_liquidities() {
+            let owner = address!("000000000000000000000000000000000000000a");
+            let permit_single = AllowanceTransferPermitSingle {
+                details: IAllowanceTransfer::PermitDetails {
+                    token: TOKEN0.address,
+                    amount: U160::from(ONE_ETHER),
+                    expiration: Default::default(),
+                    nonce: Default::default(),
+                },
+                spender: address!("000000000000000000000000000000000000000b"),
+                sigDeadline: DEADLINE,
+            };
+            let signature = Bytes::from_static(&[0xab; 65]);
+            let mut position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            let MethodParameters { calldata, .. } = add_call_parameters(
+                &mut position,
+                AddLiquidityOptions {
+                    single_permit: Some(SinglePermitOptions {
+                        owner,
+                        permit_single: permit_single.clone(),
+                        signature: signature.clone(),
+                    }),
+                    ..mint_options(Address::ZERO)
+                },
+            )
+            .unwrap();
+            let calls = decode_calls(&calldata);
+            assert_eq!(calls.len(), 2);
+            let permit = IPermit2Forwarder::permitCall::abi_decode(&calls[0], true).unwrap();
+            assert_eq!(permit.owner, owner);
+            assert_eq!(permit.permitSingle, permit_single);
+            assert_eq!(permit.signature, signature);
+            assert_eq!(
+                calls[1][..4],
+                IPositionManager::modifyLiquiditiesCall::SELECTOR
+            );
+        }
+
+        #[test]
+        fn rejects_msg_sender_as_explicit_recipient() {
+            let mut position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            let result = add_call_parameters(&mut position, mint_options(MSG_SENDER));
+            assert!(matches!(
+                result,
+                Err(Error::ReservedAddress(address)) if address == MSG_SENDER
+            ));
+        }
+    }
+
+    mod remove_call_parameters {
+        use super::*;
+
+        fn remove_options(keep_deltas_open: bool) -> RemoveLiquidityOptions {
+            RemoveLiquidityOptions {
+                common_opts: common_opts(),
+                token_id: TOKEN_ID,
+                liquidity_percentage: Percent::new(1, 1),
+                burn_token: false,
+                permit: None,
+                keep_deltas_open,
+            }
+        }
+
+        #[test]
+        fn default_matches_manual_construction() {
+            assert_eq!(
+                RemoveLiquidityOptions {
+                    common_opts: common_opts(),
+                    token_id: TOKEN_ID,
+                    ..Default::default()
+                },
+                remove_options(false)
+            );
+        }
+
+        #[test]
+        fn full_burn_matches_manual_construction() {
+            assert_eq!(
+                RemoveLiquidityOptions::full_burn(TOKEN_ID, common_opts()),
+                RemoveLiquidityOptions {
+                    burn_token: true,
+                    ..remove_options(false)
+                }
+            );
+        }
+
+        #[test]
+        fn partial_matches_manual_construction() {
+            assert_eq!(
+                RemoveLiquidityOptions::partial(TOKEN_ID, Percent::new(1, 2), common_opts()),
+                RemoveLiquidityOptions {
+                    liquidity_percentage: Percent::new(1, 2),
+                    ..remove_options(false)
+                }
+            );
+        }
+
+        #[test]
+        fn takes_pair_by_default() {
+            let position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            let MethodParameters { calldata, value } =
+                remove_call_parameters(&position, remove_options(false)).unwrap();
+            let calls = decode_calls(&calldata);
+            assert_eq!(calls.len(), 1);
+            assert_eq!(
+                modify_liquidities_commands(&calls[0]),
+                vec![
+                    Actions::DECREASE_LIQUIDITY(Default::default()).command(),
+                    Actions::TAKE_PAIR(Default::default()).command(),
+                ]
+            );
+            assert_eq!(value, U256::ZERO);
+        }
+
+        #[test]
+        fn omits_take_pair_when_keeping_deltas_open() {
+            let position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            let MethodParameters { calldata, .. } =
+                remove_call_parameters(&position, remove_options(true)).unwrap();
+            let calls = decode_calls(&calldata);
+            assert_eq!(calls.len(), 1);
+            assert_eq!(
+                modify_liquidities_commands(&calls[0]),
+                vec![Actions::DECREASE_LIQUIDITY(Default::default()).command()]
+            );
+        }
+
+        #[test]
+        fn omits_take_pair_when_burning_and_keeping_deltas_open() {
+            let position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            let MethodParameters { calldata, .. } = remove_call_parameters(
+                &position,
+                RemoveLiquidityOptions {
+                    burn_token: true,
+                    ..remove_options(true)
+                },
+            )
+            .unwrap();
+            let calls = decode_calls(&calldata);
+            assert_eq!(
+                modify_liquidities_commands(&calls[0]),
+                vec![Actions::BURN_POSITION(Default::default()).command()]
+            );
+        }
+    }
+
+    mod collect_call_parameters {
+        use super::*;
+
+        #[test]
+        fn new_matches_manual_construction() {
+            let recipient = address!("0000000000000000000000000000000000000003");
+            assert_eq!(
+                CollectOptions::new(TOKEN_ID, recipient, common_opts()),
+                CollectOptions {
+                    common_opts: common_opts(),
+                    token_id: TOKEN_ID,
+                    recipient,
+                }
+            );
+        }
+
+        #[test]
+        fn to_caller_takes_to_msg_sender() {
+            let position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            let options = CollectOptions::to_caller(TOKEN_ID, common_opts());
+            assert_eq!(options.recipient, MSG_SENDER);
+            let MethodParameters { calldata, value } = collect_call_parameters(&position, options);
+            assert_eq!(value, U256::ZERO);
+            assert_eq!(
+                parse_modify_liquidities(&calldata)[1],
+                Actions::TAKE_PAIR(TakePairParams {
+                    currency0: TOKEN0.address,
+                    currency1: TOKEN1.address,
+                    recipient: MSG_SENDER,
+                })
+            );
+        }
+
+        #[test]
+        fn new_takes_to_the_given_owner() {
+            let owner = address!("0000000000000000000000000000000000000003");
+            let position = Position::new(POOL_0_1.clone(), ONE_ETHER, -60, 60);
+            let MethodParameters { calldata, .. } = collect_call_parameters(
+                &position,
+                CollectOptions::new(TOKEN_ID, owner, common_opts()),
+            );
+            assert_eq!(
+                parse_modify_liquidities(&calldata)[1],
+                Actions::TAKE_PAIR(TakePairParams {
+                    currency0: TOKEN0.address,
+                    currency1: TOKEN1.address,
+                    recipient: owner,
+                })
+            );
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use alloy_primitives::Address;
 use uniswap_sdk_core::prelude::BaseCurrency;
 
+/// Returns the address used to identify the currency in v4 calldata, i.e. [`Address::ZERO`] for
+/// the native currency and the token address otherwise
 #[inline]
 pub fn to_address(currency: &impl BaseCurrency) -> Address {
     match currency.is_native() {
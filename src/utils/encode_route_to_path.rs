@@ -1,18 +1,32 @@
-use crate::prelude::{PathKey, Pool, Route};
-use alloy_primitives::{Address, Bytes, U256};
+use crate::prelude::{to_address, Error, PathKey, Pool, Route};
+use alloy_primitives::{Bytes, U256};
 use uniswap_sdk_core::prelude::*;
 use uniswap_v3_sdk::prelude::*;
 
+/// Encodes the pools of a route as the path keys of a multi-hop swap
+///
+/// ## Arguments
+///
+/// * `route`: The route to encode
+/// * `exact_output`: Whether the path is for an exact output swap, in which case it is ordered
+///   from the output currency back to the input currency
+///
+/// ## Errors
+///
+/// Returns [`Error::EmptyRoute`] if the route has no pools.
 #[inline]
 pub fn encode_route_to_path<TInput, TOutput, TP>(
     route: &Route<TInput, TOutput, TP>,
     exact_output: bool,
-) -> Vec<PathKey>
+) -> Result<Vec<PathKey>, Error>
 where
     TInput: BaseCurrency,
     TOutput: BaseCurrency,
     TP: TickDataProvider,
 {
+    if route.pools.is_empty() {
+        return Err(Error::EmptyRoute);
+    }
     let mut path_keys: Vec<PathKey> = Vec::with_capacity(route.pools.len());
     if exact_output {
         let mut output_currency = &route.path_output;
@@ -30,7 +44,7 @@ where
             input_currency = next_currency;
         }
     }
-    path_keys
+    Ok(path_keys)
 }
 
 #[inline]
@@ -50,11 +64,7 @@ where
     (
         next_currency,
         PathKey {
-            intermediateCurrency: if next_currency.is_native() {
-                Address::ZERO
-            } else {
-                next_currency.address()
-            },
+            intermediateCurrency: to_address(next_currency),
             fee: U256::from(pool.fee),
             tickSpacing: pool.tick_spacing.to_i24(),
             hooks: pool.hooks,
@@ -148,7 +158,7 @@ mod tests {
             },
         ];
 
-        assert_eq!(encode_route_to_path(&ROUTE, false), expected);
+        assert_eq!(encode_route_to_path(&ROUTE, false).unwrap(), expected);
     }
 
     #[test]
@@ -177,7 +187,7 @@ mod tests {
             },
         ];
 
-        assert_eq!(encode_route_to_path(&ROUTE, true), expected);
+        assert_eq!(encode_route_to_path(&ROUTE, true).unwrap(), expected);
     }
 
     #[test]
@@ -206,7 +216,10 @@ mod tests {
             },
         ];
 
-        assert_eq!(encode_route_to_path(&new_route, exact_output), expected);
+        assert_eq!(
+            encode_route_to_path(&new_route, exact_output).unwrap(),
+            expected
+        );
     }
 
     #[test]
@@ -235,6 +248,74 @@ mod tests {
             },
         ];
 
-        assert_eq!(encode_route_to_path(&new_route, exact_output), expected);
+        assert_eq!(
+            encode_route_to_path(&new_route, exact_output).unwrap(),
+            expected
+        );
+    }
+
+    mod reversed_route {
+        use super::*;
+
+        static REVERSED_ROUTE: Lazy<Route<Currency, Ether, NoTickDataProvider>> = Lazy::new(|| {
+            Route::new(
+                vec![POOL_2_3.clone(), POOL_1_2.clone(), POOL_ETH_1.clone()],
+                CURRENCY3.clone().into(),
+                ETHER.clone(),
+            )
+            .unwrap()
+        });
+
+        fn intermediate_currencies(path: &[PathKey]) -> Vec<Address> {
+            path.iter().map(|key| key.intermediateCurrency).collect()
+        }
+
+        #[test]
+        fn encodes_correct_route_for_exact_in() {
+            assert_eq!(
+                intermediate_currencies(&encode_route_to_path(&REVERSED_ROUTE, false).unwrap()),
+                vec![CURRENCY2.address(), CURRENCY1.address(), Address::ZERO]
+            );
+        }
+
+        #[test]
+        fn encodes_correct_route_for_exact_out() {
+            assert_eq!(
+                intermediate_currencies(&encode_route_to_path(&REVERSED_ROUTE, true).unwrap()),
+                vec![
+                    CURRENCY3.address(),
+                    CURRENCY2.address(),
+                    CURRENCY1.address()
+                ]
+            );
+        }
+
+        #[test]
+        fn exact_out_path_is_reversed_exact_in_path_of_reversed_route() {
+            let mut exact_in = encode_route_to_path(&REVERSED_ROUTE, false).unwrap();
+            exact_in.reverse();
+            assert_eq!(encode_route_to_path(&ROUTE, true).unwrap(), exact_in);
+
+            let mut exact_in = encode_route_to_path(&ROUTE, false).unwrap();
+            exact_in.reverse();
+            assert_eq!(
+                encode_route_to_path(&REVERSED_ROUTE, true).unwrap(),
+                exact_in
+            );
+        }
+    }
+
+    #[test]
+    fn errors_on_empty_route() {
+        let mut route = ROUTE.clone();
+        route.pools.clear();
+        assert!(matches!(
+            encode_route_to_path(&route, false),
+            Err(Error::EmptyRoute)
+        ));
+        assert!(matches!(
+            encode_route_to_path(&route, true),
+            Err(Error::EmptyRoute)
+        ));
     }
 }
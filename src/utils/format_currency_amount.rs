@@ -0,0 +1,41 @@
+use alloc::{format, string::String};
+use uniswap_sdk_core::prelude::{BaseCurrency, CurrencyAmount};
+
+/// Formats a currency amount with its exact decimal value followed by the currency symbol, e.g.
+/// `"1.2345 USDC"`. The symbol is omitted if the currency has none.
+///
+/// ## Arguments
+///
+/// * `amount`: The currency amount to format
+#[inline]
+pub fn format_currency_amount(amount: &CurrencyAmount<impl BaseCurrency>) -> String {
+    match amount.currency.symbol() {
+        Some(symbol) => format!("{} {}", amount.to_exact(), symbol),
+        None => amount.to_exact(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn formats_usdc_amount() {
+        let amount = CurrencyAmount::from_raw_amount(USDC.clone(), 1_234_500).unwrap();
+        assert_eq!(format_currency_amount(&amount), "1.2345 USDC");
+    }
+
+    #[test]
+    fn formats_dai_amount() {
+        let amount =
+            CurrencyAmount::from_raw_amount(DAI.clone(), 1_500_000_000_000_000_001_u128).unwrap();
+        assert_eq!(format_currency_amount(&amount), "1.500000000000000001 DAI");
+    }
+
+    #[test]
+    fn formats_whole_amount() {
+        let amount = CurrencyAmount::from_raw_amount(DAI.clone(), 42 * ONE_ETHER).unwrap();
+        assert_eq!(format_currency_amount(&amount), "42 DAI");
+    }
+}
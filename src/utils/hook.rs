@@ -1,4 +1,6 @@
-use alloy_primitives::Address;
+use crate::prelude::DYANMIC_FEE_FLAG;
+use alloy_primitives::{aliases::U24, Address};
+use core::fmt;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(u8)]
@@ -19,7 +21,39 @@ pub enum HookOptions {
     BeforeInitialize = 13,
 }
 
+impl HookOptions {
+    /// Returns all hook options, ordered by flag index
+    #[inline]
+    #[must_use]
+    pub const fn all() -> [Self; 14] {
+        [
+            Self::AfterRemoveLiquidityReturnsDelta,
+            Self::AfterAddLiquidityReturnsDelta,
+            Self::AfterSwapReturnsDelta,
+            Self::BeforeSwapReturnsDelta,
+            Self::AfterDonate,
+            Self::BeforeDonate,
+            Self::AfterSwap,
+            Self::BeforeSwap,
+            Self::AfterRemoveLiquidity,
+            Self::BeforeRemoveLiquidity,
+            Self::AfterAddLiquidity,
+            Self::BeforeAddLiquidity,
+            Self::AfterInitialize,
+            Self::BeforeInitialize,
+        ]
+    }
+
+    /// Returns an iterator over all hook options, ordered by flag index
+    #[inline]
+    pub fn iter() -> impl Iterator<Item = Self> {
+        Self::all().into_iter()
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct HookPermissions {
     pub after_remove_liquidity_returns_delta: bool,
     pub after_add_liquidity_returns_delta: bool,
@@ -37,6 +71,41 @@ pub struct HookPermissions {
     pub before_initialize: bool,
 }
 
+impl fmt::Display for HookPermissions {
+    /// Lists the enabled permissions in hook lifecycle order, e.g. `beforeSwap, afterSwap`
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = [
+            (self.before_initialize, "beforeInitialize"),
+            (self.after_initialize, "afterInitialize"),
+            (self.before_add_liquidity, "beforeAddLiquidity"),
+            (self.after_add_liquidity, "afterAddLiquidity"),
+            (self.before_remove_liquidity, "beforeRemoveLiquidity"),
+            (self.after_remove_liquidity, "afterRemoveLiquidity"),
+            (self.before_swap, "beforeSwap"),
+            (self.after_swap, "afterSwap"),
+            (self.before_donate, "beforeDonate"),
+            (self.after_donate, "afterDonate"),
+            (self.before_swap_returns_delta, "beforeSwapReturnsDelta"),
+            (self.after_swap_returns_delta, "afterSwapReturnsDelta"),
+            (
+                self.after_add_liquidity_returns_delta,
+                "afterAddLiquidityReturnsDelta",
+            ),
+            (
+                self.after_remove_liquidity_returns_delta,
+                "afterRemoveLiquidityReturnsDelta",
+            ),
+        ];
+        let mut separator = "";
+        for (_, name) in flags.into_iter().filter(|(enabled, _)| *enabled) {
+            write!(f, "{separator}{name}")?;
+            separator = ", ";
+        }
+        Ok(())
+    }
+}
+
 #[inline]
 #[must_use]
 pub const fn permissions(address: Address) -> HookPermissions {
@@ -103,6 +172,45 @@ pub const fn has_donate_permissions(address: Address) -> bool {
         || has_permission(address, HookOptions::AfterDonate)
 }
 
+/// Returns whether the hook address is valid for a pool with the given fee, mirroring
+/// `Hooks.isValidHookAddress`, which the pool manager checks when a pool is initialized
+///
+/// A returns-delta flag requires its corresponding action flag. Without a hook contract the fee
+/// cannot be dynamic, and a hook contract must have at least one flag or a dynamic fee.
+///
+/// ## Arguments
+///
+/// * `address`: The hook address
+/// * `fee`: The fee of the pool, possibly [`DYANMIC_FEE_FLAG`]
+#[inline]
+#[must_use]
+pub fn is_valid_hook_address(address: Address, fee: U24) -> bool {
+    let requires = [
+        (HookOptions::BeforeSwapReturnsDelta, HookOptions::BeforeSwap),
+        (HookOptions::AfterSwapReturnsDelta, HookOptions::AfterSwap),
+        (
+            HookOptions::AfterAddLiquidityReturnsDelta,
+            HookOptions::AfterAddLiquidity,
+        ),
+        (
+            HookOptions::AfterRemoveLiquidityReturnsDelta,
+            HookOptions::AfterRemoveLiquidity,
+        ),
+    ];
+    if requires.into_iter().any(|(returns_delta, action)| {
+        has_permission(address, returns_delta) && !has_permission(address, action)
+    }) {
+        return false;
+    }
+    let is_dynamic_fee = fee == DYANMIC_FEE_FLAG;
+    if address == Address::ZERO {
+        !is_dynamic_fee
+    } else {
+        HookOptions::iter().any(|hook_option| has_permission(address, hook_option))
+            || is_dynamic_fee
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,6 +400,62 @@ mod tests {
         }
     }
 
+    mod all {
+        use super::*;
+
+        #[test]
+        fn has_14_distinct_variants_covering_all_flag_indices() {
+            let all = HookOptions::all();
+            assert_eq!(all.len(), 14);
+            for (index, hook_option) in all.into_iter().enumerate() {
+                assert_eq!(hook_option as usize, index);
+            }
+            assert!(HookOptions::iter().eq(all));
+        }
+
+        #[test]
+        fn iter_covers_all_permissions() {
+            assert!(HookOptions::iter()
+                .all(|hook_option| has_permission(ALL_HOOKS_ADDRESS, hook_option)));
+            assert_eq!(
+                construct_hook_address(HookOptions::iter().collect()),
+                ALL_HOOKS_ADDRESS
+            );
+        }
+    }
+
+    mod display {
+        use super::*;
+
+        #[test]
+        fn lists_enabled_permissions() {
+            let permissions = HookPermissions {
+                before_swap: true,
+                after_swap: true,
+                ..Default::default()
+            };
+            assert_eq!(permissions.to_string(), "beforeSwap, afterSwap");
+        }
+
+        #[test]
+        fn lists_permissions_in_lifecycle_order() {
+            assert_eq!(
+                permissions(construct_hook_address(vec![
+                    HookOptions::AfterSwapReturnsDelta,
+                    HookOptions::AfterSwap,
+                    HookOptions::BeforeSwap,
+                ]))
+                .to_string(),
+                "beforeSwap, afterSwap, afterSwapReturnsDelta"
+            );
+        }
+
+        #[test]
+        fn empty_without_permissions() {
+            assert_eq!(permissions(EMPTY_HOOK_ADDRESS).to_string(), "");
+        }
+    }
+
     mod has_permission {
         use super::*;
 
@@ -536,4 +700,51 @@ mod tests {
             assert!(!has_donate_permissions(*HOOK_AFTER_SWAP));
         }
     }
+
+    mod is_valid_hook_address {
+        use super::*;
+        use alloy_primitives::uint;
+
+        const FEE: U24 = uint!(3000_U24);
+
+        #[test]
+        fn no_hook_with_static_fee() {
+            assert!(is_valid_hook_address(EMPTY_HOOK_ADDRESS, FEE));
+            assert!(!is_valid_hook_address(EMPTY_HOOK_ADDRESS, DYANMIC_FEE_FLAG));
+        }
+
+        #[test]
+        fn hook_needs_a_flag_or_dynamic_fee() {
+            let no_flags = address!("0000000000000000000000000000000000010000");
+            assert!(!is_valid_hook_address(no_flags, FEE));
+            assert!(is_valid_hook_address(no_flags, DYANMIC_FEE_FLAG));
+            assert!(is_valid_hook_address(*HOOK_BEFORE_SWAP, FEE));
+            assert!(is_valid_hook_address(ALL_HOOKS_ADDRESS, FEE));
+        }
+
+        #[test]
+        fn returns_delta_flags_need_their_action_flag() {
+            for (returns_delta, action) in [
+                (HookOptions::BeforeSwapReturnsDelta, HookOptions::BeforeSwap),
+                (HookOptions::AfterSwapReturnsDelta, HookOptions::AfterSwap),
+                (
+                    HookOptions::AfterAddLiquidityReturnsDelta,
+                    HookOptions::AfterAddLiquidity,
+                ),
+                (
+                    HookOptions::AfterRemoveLiquidityReturnsDelta,
+                    HookOptions::AfterRemoveLiquidity,
+                ),
+            ] {
+                assert!(!is_valid_hook_address(
+                    construct_hook_address(vec![returns_delta]),
+                    FEE
+                ));
+                assert!(is_valid_hook_address(
+                    construct_hook_address(vec![returns_delta, action]),
+                    FEE
+                ));
+            }
+        }
+    }
 }
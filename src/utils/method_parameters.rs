@@ -0,0 +1,63 @@
+use uniswap_v3_sdk::prelude::MethodParameters;
+
+/// Calldata length above which [`MethodParametersExt::is_likely_oversized`] flags a call, in
+/// bytes.
+///
+/// This is a heuristic rather than a protocol limit: calldata is charged up to 16 gas per byte, so
+/// a call this large spends well over a million gas on calldata alone and is a good candidate for
+/// splitting into several transactions.
+pub const OVERSIZED_CALLDATA_LEN: usize = 100 * 1024;
+
+/// Size accessors for [`MethodParameters`], e.g. to decide when to split a batched multicall
+pub trait MethodParametersExt {
+    /// Returns the length of the calldata in bytes
+    fn calldata_len(&self) -> usize;
+
+    /// Returns whether the calldata exceeds [`OVERSIZED_CALLDATA_LEN`]
+    fn is_likely_oversized(&self) -> bool;
+}
+
+impl MethodParametersExt for MethodParameters {
+    #[inline]
+    fn calldata_len(&self) -> usize {
+        self.calldata.len()
+    }
+
+    #[inline]
+    fn is_likely_oversized(&self) -> bool {
+        self.calldata_len() > OVERSIZED_CALLDATA_LEN
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, tests::*};
+    use alloy_primitives::{Bytes, U256};
+
+    #[test]
+    fn small_call_is_not_oversized() {
+        let method_parameters =
+            create_call_parameters(USDC_DAI.pool_key.clone(), USDC_DAI.sqrt_price_x96);
+        assert_eq!(
+            method_parameters.calldata_len(),
+            method_parameters.calldata.len()
+        );
+        assert!(!method_parameters.is_likely_oversized());
+    }
+
+    #[test]
+    fn large_multicall_is_oversized() {
+        let calls = vec![Bytes::from(vec![0xab; 1024]); 120];
+        let method_parameters = MethodParameters {
+            calldata: encode_multicall(calls),
+            value: U256::ZERO,
+        };
+        // selector, offset and length of the array, 120 offsets, then 120 length-prefixed calls
+        assert_eq!(
+            method_parameters.calldata_len(),
+            4 + 32 + 32 + 120 * 32 + 120 * (32 + 1024)
+        );
+        assert!(method_parameters.is_likely_oversized());
+    }
+}
@@ -1,8 +1,12 @@
 pub mod currency_map;
 pub mod encode_route_to_path;
+pub mod format_currency_amount;
 pub mod hook;
+pub mod method_parameters;
 pub mod path_currency;
+pub mod pool_id_memo;
 pub mod price_tick_conversions;
+pub mod shared_tick_data_provider;
 pub mod sorts_before;
 pub mod v4_base_actions_parser;
 pub mod v4_planner;
@@ -10,9 +14,13 @@ pub mod v4_position_planner;
 
 pub use currency_map::*;
 pub use encode_route_to_path::*;
+pub use format_currency_amount::*;
 pub use hook::*;
+pub use method_parameters::*;
 pub use path_currency::*;
+pub use pool_id_memo::*;
 pub use price_tick_conversions::*;
+pub use shared_tick_data_provider::*;
 pub use sorts_before::*;
 pub use v4_base_actions_parser::*;
 pub use v4_planner::*;
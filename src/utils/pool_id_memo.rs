@@ -0,0 +1,103 @@
+use crate::prelude::PoolKey;
+use alloy_primitives::{keccak256, B256};
+use alloy_sol_types::SolValue;
+use rustc_hash::FxHashMap;
+
+/// A memo of pool ids keyed by [`PoolKey`].
+///
+/// A [`Pool`](crate::prelude::Pool) already carries its `pool_id`, so this is only useful in hot
+/// loops that derive ids from bare pool keys repeatedly, e.g. when indexing pools decoded from
+/// calldata or event logs. Each distinct key is hashed once; later lookups are a map access.
+#[derive(Clone, Debug, Default)]
+pub struct PoolIdMemo {
+    ids: FxHashMap<PoolKey, B256>,
+    hashes_computed: usize,
+}
+
+impl PoolIdMemo {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the pool id of the given pool key, computing and memoizing it on first use
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool_key`: The pool key to look up
+    #[inline]
+    pub fn pool_id(&mut self, pool_key: &PoolKey) -> B256 {
+        if let Some(pool_id) = self.ids.get(pool_key) {
+            return *pool_id;
+        }
+        let pool_id = keccak256(pool_key.abi_encode());
+        self.hashes_computed += 1;
+        self.ids.insert(pool_key.clone(), pool_id);
+        pool_id
+    }
+
+    /// Returns the number of memoized pool ids
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Returns the number of pool ids that had to be hashed, i.e. the number of memo misses
+    #[inline]
+    #[must_use]
+    pub const fn hashes_computed(&self) -> usize {
+        self.hashes_computed
+    }
+
+    /// Removes all memoized pool ids
+    #[inline]
+    pub fn clear(&mut self) {
+        self.ids.clear();
+        self.hashes_computed = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn returns_the_pool_id() {
+        let mut memo = PoolIdMemo::new();
+        assert_eq!(memo.pool_id(&USDC_DAI.pool_key), USDC_DAI.pool_id);
+    }
+
+    #[test]
+    fn returns_consistent_ids_and_hashes_each_key_once() {
+        let mut memo = PoolIdMemo::new();
+        for _ in 0..10 {
+            assert_eq!(memo.pool_id(&USDC_DAI.pool_key), USDC_DAI.pool_id);
+            assert_eq!(memo.pool_id(&DAI_USDC.pool_key), DAI_USDC.pool_id);
+        }
+        assert_eq!(memo.len(), 1);
+        assert_eq!(memo.hashes_computed(), 1);
+
+        let other_key = PoolKey {
+            hooks: alloy_primitives::address!("0000000000000000000000000000000000000001"),
+            ..USDC_DAI.pool_key.clone()
+        };
+        let other_id = memo.pool_id(&other_key);
+        assert_ne!(other_id, USDC_DAI.pool_id);
+        assert_eq!(memo.pool_id(&other_key), other_id);
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.hashes_computed(), 2);
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.hashes_computed(), 0);
+    }
+}
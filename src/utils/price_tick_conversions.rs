@@ -1,8 +1,8 @@
 //! ## Price and tick conversions
 //! Utility functions for converting between [`I24`] ticks and SDK Core [`Price`] prices.
 
-use crate::prelude::{sorts_before, Error};
-use alloy_primitives::{aliases::I24, U160};
+use crate::prelude::{sorts_before, Error, Pool};
+use alloy_primitives::{aliases::I24, U160, U256};
 use uniswap_sdk_core::prelude::*;
 use uniswap_v3_sdk::prelude::*;
 
@@ -64,3 +64,217 @@ pub fn price_to_closest_tick(price: &Price<Currency, Currency>) -> Result<I24, E
         tick
     })
 }
+
+/// Returns the prices of currency0 in terms of currency1 at the lower and upper ticks of a
+/// position, i.e. the min and max prices of its range
+///
+/// ## Arguments
+///
+/// * `pool`: The pool of the position
+/// * `tick_lower`: The lower tick of the position
+/// * `tick_upper`: The upper tick of the position
+#[inline]
+pub fn position_price_range<TP: TickDataProvider>(
+    pool: &Pool<TP>,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Result<(Price<Currency, Currency>, Price<Currency, Currency>), Error> {
+    assert!(tick_lower < tick_upper, "TICK_ORDER");
+    Ok((
+        tick_to_price(
+            pool.currency0.clone(),
+            pool.currency1.clone(),
+            tick_lower.to_i24(),
+        )?,
+        tick_to_price(
+            pool.currency0.clone(),
+            pool.currency1.clone(),
+            tick_upper.to_i24(),
+        )?,
+    ))
+}
+
+/// Returns the sqrt ratio as a Q64.96 corresponding to a human readable price, scaling each side
+/// by its decimals, e.g. `(1, 3000, 18, 6)` for 3000 USDC per ETH.
+///
+/// The base currency must be currency0 of the pool, so that the result is the price of
+/// currency0 in terms of currency1 as stored by the pool.
+///
+/// ## Arguments
+///
+/// * `base_amount`: The amount of the base currency, in whole units
+/// * `quote_amount`: The amount of the quote currency worth `base_amount`, in whole units
+/// * `base_decimals`: The decimals of the base currency
+/// * `quote_decimals`: The decimals of the quote currency
+///
+/// ## Errors
+///
+/// Returns [`Error::ZeroPriceAmount`] if either amount is zero, and [`Error::SqrtPriceOverflow`]
+/// if the resulting sqrt price exceeds [`U160::MAX`].
+#[inline]
+pub fn price_to_sqrt_ratio_x96(
+    base_amount: U256,
+    quote_amount: U256,
+    base_decimals: u8,
+    quote_decimals: u8,
+) -> Result<U160, Error> {
+    if base_amount.is_zero() || quote_amount.is_zero() {
+        return Err(Error::ZeroPriceAmount);
+    }
+    // scale in `BigInt` so that large amounts or decimals cannot overflow
+    let ten = BigInt::from(10);
+    let amount1 = quote_amount.to_big_int() * ten.pow(u32::from(quote_decimals));
+    let amount0 = base_amount.to_big_int() * ten.pow(u32::from(base_decimals));
+    // the sqrt of the Q192 ratio fits in 160 bits iff the ratio is below 2^320
+    if (amount1.clone() << 192) / amount0.clone() >= BigInt::from(1) << 320 {
+        return Err(Error::SqrtPriceOverflow);
+    }
+    Ok(encode_sqrt_ratio_x96(amount1, amount0))
+}
+
+/// Widens a Q64.96 sqrt price to a [`U256`] for intermediate math
+///
+/// ## Arguments
+///
+/// * `sqrt_price_x96`: The sqrt price
+#[inline]
+#[must_use]
+pub fn sqrt_price_to_u256(sqrt_price_x96: U160) -> U256 {
+    U256::from(sqrt_price_x96)
+}
+
+/// Narrows a [`U256`] back to a Q64.96 sqrt price
+///
+/// ## Arguments
+///
+/// * `x`: The sqrt price as a [`U256`]
+///
+/// ## Errors
+///
+/// Returns [`Error::SqrtPriceOverflow`] if `x` exceeds [`U160::MAX`].
+#[inline]
+pub fn u256_to_sqrt_price(x: U256) -> Result<U160, Error> {
+    if x > sqrt_price_to_u256(U160::MAX) {
+        return Err(Error::SqrtPriceOverflow);
+    }
+    Ok(U160::from(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn position_price_range_of_eth_usdc_position() {
+        let pool = Pool::new(
+            ETHER.clone().into(),
+            USDC.clone().into(),
+            FeeAmount::MEDIUM.into(),
+            60,
+            Address::ZERO,
+            encode_sqrt_ratio_x96(3_000_000_000_u64, ONE_ETHER),
+            0,
+        )
+        .unwrap();
+        let (lower, upper) = position_price_range(&pool, -200_040, -193_140).unwrap();
+        assert!(lower.base_currency.equals(&ETHER.clone()));
+        assert!(lower.quote_currency.equals(&USDC.clone()));
+        assert!(lower < upper);
+        assert_eq!(lower.to_fixed(0, None), "2055");
+        assert_eq!(upper.to_fixed(0, None), "4097");
+    }
+
+    #[test]
+    fn price_to_sqrt_ratio_x96_scales_by_decimals() {
+        let sqrt_ratio_x96 =
+            price_to_sqrt_ratio_x96(U256::from(1), U256::from(3000), 18, USDC.decimals).unwrap();
+        assert_eq!(
+            sqrt_ratio_x96,
+            encode_sqrt_ratio_x96(3_000_000_000_u64, ONE_ETHER)
+        );
+
+        let price: Price<Currency, Currency> = Price::new(
+            ETHER.clone().into(),
+            USDC.clone().into(),
+            Q192.to_big_uint(),
+            sqrt_ratio_x96.to_big_uint().pow(2),
+        );
+        assert_eq!(price.to_significant(6, None).unwrap(), "3000");
+    }
+
+    #[test]
+    fn price_to_sqrt_ratio_x96_with_more_quote_decimals() {
+        assert_eq!(
+            price_to_sqrt_ratio_x96(U256::from(1), U256::from(1), 6, 18).unwrap(),
+            encode_sqrt_ratio_x96(ONE_ETHER, 1_000_000)
+        );
+        assert_eq!(
+            price_to_sqrt_ratio_x96(U256::from(2), U256::from(1), 6, 6).unwrap(),
+            encode_sqrt_ratio_x96(1, 2)
+        );
+    }
+
+    #[test]
+    fn price_to_sqrt_ratio_x96_rejects_zero_amounts() {
+        assert!(matches!(
+            price_to_sqrt_ratio_x96(U256::ZERO, U256::from(3000), 18, 6),
+            Err(Error::ZeroPriceAmount)
+        ));
+        assert!(matches!(
+            price_to_sqrt_ratio_x96(U256::from(1), U256::ZERO, 18, 6),
+            Err(Error::ZeroPriceAmount)
+        ));
+    }
+
+    #[test]
+    fn price_to_sqrt_ratio_x96_does_not_overflow_when_scaling() {
+        // both sides overflow `U256` when scaled, but the price is 1
+        assert_eq!(
+            price_to_sqrt_ratio_x96(U256::MAX, U256::MAX, u8::MAX, u8::MAX).unwrap(),
+            encode_sqrt_ratio_x96(1, 1)
+        );
+        // 10^255 quote per base unit overflows uint160
+        assert!(matches!(
+            price_to_sqrt_ratio_x96(U256::from(1), U256::from(1), 0, u8::MAX),
+            Err(Error::SqrtPriceOverflow)
+        ));
+        // a price below 2^128 still fits
+        assert_eq!(
+            price_to_sqrt_ratio_x96(U256::from(1), U256::from(u128::MAX), 0, 0).unwrap(),
+            encode_sqrt_ratio_x96(u128::MAX, 1)
+        );
+        assert!(matches!(
+            price_to_sqrt_ratio_x96(U256::from(1), U256::MAX, 0, 0),
+            Err(Error::SqrtPriceOverflow)
+        ));
+    }
+
+    #[test]
+    fn sqrt_price_round_trips_through_u256() {
+        for sqrt_price_x96 in [
+            U160::ZERO,
+            MIN_SQRT_RATIO,
+            encode_sqrt_ratio_x96(1, 1),
+            MAX_SQRT_RATIO,
+            U160::MAX,
+        ] {
+            let widened = sqrt_price_to_u256(sqrt_price_x96);
+            assert_eq!(widened.to_big_uint(), sqrt_price_x96.to_big_uint());
+            assert_eq!(u256_to_sqrt_price(widened).unwrap(), sqrt_price_x96);
+        }
+    }
+
+    #[test]
+    fn u256_to_sqrt_price_rejects_overflow() {
+        let max = sqrt_price_to_u256(U160::MAX);
+        assert!(matches!(
+            u256_to_sqrt_price(max + U256::from(1)),
+            Err(Error::SqrtPriceOverflow)
+        ));
+        assert!(matches!(
+            u256_to_sqrt_price(U256::MAX),
+            Err(Error::SqrtPriceOverflow)
+        ));
+    }
+}
@@ -0,0 +1,39 @@
+use alloc::sync::Arc;
+use uniswap_v3_sdk::{
+    error::Error,
+    prelude::{Tick, TickDataProvider},
+};
+
+/// A [`TickDataProvider`] behind an [`Arc`], so that cloning a pool does not clone its tick data.
+///
+/// See [`Pool::share_tick_data`](crate::prelude::Pool::share_tick_data).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SharedTickDataProvider<TP>(pub Arc<TP>);
+
+impl<TP> SharedTickDataProvider<TP> {
+    #[inline]
+    #[must_use]
+    pub fn new(tick_data_provider: TP) -> Self {
+        Self(Arc::new(tick_data_provider))
+    }
+}
+
+impl<TP: TickDataProvider> TickDataProvider for SharedTickDataProvider<TP> {
+    type Index = TP::Index;
+
+    #[inline]
+    fn get_tick(&self, tick: Self::Index) -> Result<&Tick<Self::Index>, Error> {
+        self.0.get_tick(tick)
+    }
+
+    #[inline]
+    fn next_initialized_tick_within_one_word(
+        &self,
+        tick: Self::Index,
+        lte: bool,
+        tick_spacing: Self::Index,
+    ) -> Result<(Self::Index, bool), Error> {
+        self.0
+            .next_initialized_tick_within_one_word(tick, lte, tick_spacing)
+    }
+}
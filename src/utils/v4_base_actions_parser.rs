@@ -9,6 +9,16 @@ pub struct V4RouterCall {
     pub actions: Vec<Actions>,
 }
 
+/// Parses the encoded actions and params of a V4 router or position manager call
+///
+/// ## Arguments
+///
+/// * `calldata`: The abi encoded [`ActionsParams`]
+///
+/// ## Errors
+///
+/// Returns [`Error::Sol`] if the calldata is malformed, [`Error::MalformedActionParams`] if any
+/// action params are malformed, and [`Error::InvalidAction`] if an action is not supported.
 #[inline]
 pub fn parse_calldata(calldata: &Bytes) -> Result<V4RouterCall, Error> {
     let ActionsParams { actions, params } =
@@ -109,7 +119,8 @@ mod tests {
                     )
                     .unwrap(),
                     false,
-                ),
+                )
+                .unwrap(),
                 amountIn: AMOUNT.try_into().unwrap(),
                 amountOutMinimum: AMOUNT.try_into().unwrap(),
             }),
@@ -123,7 +134,8 @@ mod tests {
                     )
                     .unwrap(),
                     false,
-                ),
+                )
+                .unwrap(),
                 amountOut: AMOUNT.try_into().unwrap(),
                 amountInMaximum: AMOUNT.try_into().unwrap(),
             }),
@@ -137,4 +149,279 @@ mod tests {
             assert_eq!(result.actions, vec![test]);
         }
     }
+
+    #[test]
+    fn test_parse_truncated_calldata() {
+        let mut planner = V4Planner::default();
+        planner.add_action(&Actions::SWEEP(SweepParams {
+            currency: ADDRESS_ONE,
+            recipient: ADDRESS_TWO,
+        }));
+        let calldata = planner.finalize();
+        let truncated = Bytes::copy_from_slice(&calldata[..calldata.len() - 1]);
+        assert!(matches!(parse_calldata(&truncated), Err(Error::Sol(_))));
+    }
+
+    #[test]
+    fn test_parse_truncated_action_params() {
+        let mut planner = V4Planner::default();
+        planner.actions.push(0x14);
+        planner.params.push(Bytes::from_static(&[0; 20]));
+        let calldata = planner.finalize();
+        assert!(matches!(
+            parse_calldata(&calldata),
+            Err(Error::MalformedActionParams { opcode: 0x14 })
+        ));
+    }
+
+    #[test]
+    fn test_parse_mismatched_action_params() {
+        let settle = Actions::SETTLE(SettleParams {
+            currency: ADDRESS_ONE,
+            amount: AMOUNT,
+            payerIsUser: true,
+        });
+        let mut planner = V4Planner::default();
+        planner
+            .actions
+            .push(Actions::MINT_POSITION(Default::default()).command());
+        planner.params.push(settle.abi_encode());
+        let calldata = planner.finalize();
+        assert!(matches!(
+            parse_calldata(&calldata),
+            Err(Error::MalformedActionParams { opcode: 0x02 })
+        ));
+    }
+
+    #[test]
+    fn test_parse_action_params_with_trailing_bytes() {
+        let settle = Actions::SETTLE(SettleParams {
+            currency: ADDRESS_ONE,
+            amount: AMOUNT,
+            payerIsUser: true,
+        });
+        let mut params = settle.abi_encode().to_vec();
+        params.extend_from_slice(&[0; 32]);
+        let mut planner = V4Planner::default();
+        planner.actions.push(settle.command());
+        planner.params.push(params.into());
+        let calldata = planner.finalize();
+        assert!(matches!(
+            parse_calldata(&calldata),
+            Err(Error::MalformedActionParams { opcode: 0x0b })
+        ));
+    }
+
+    mod round_trip {
+        use super::*;
+        use alloy_primitives::aliases::{I24, U24};
+
+        /// Number of [`Actions`] variants, i.e. the number of distinct commands generated
+        const NUM_ACTIONS: usize = 18;
+
+        /// A xorshift64* generator, so that the sequences are reproducible without a fuzzing
+        /// dependency
+        struct Rng(u64);
+
+        impl Rng {
+            fn next_u64(&mut self) -> u64 {
+                self.0 ^= self.0 >> 12;
+                self.0 ^= self.0 << 25;
+                self.0 ^= self.0 >> 27;
+                self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+            }
+
+            fn below(&mut self, n: u64) -> u64 {
+                self.next_u64() % n
+            }
+
+            fn bool(&mut self) -> bool {
+                self.next_u64() & 1 == 1
+            }
+
+            fn u128(&mut self) -> u128 {
+                (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64())
+            }
+
+            fn u256(&mut self) -> U256 {
+                U256::from_limbs([
+                    self.next_u64(),
+                    self.next_u64(),
+                    self.next_u64(),
+                    self.next_u64(),
+                ])
+            }
+
+            fn u160(&mut self) -> U160 {
+                U160::from_limbs([
+                    self.next_u64(),
+                    self.next_u64(),
+                    self.next_u64() & 0xffff_ffff,
+                ])
+            }
+
+            fn address(&mut self) -> Address {
+                Address::from(self.u160())
+            }
+
+            fn tick(&mut self) -> I24 {
+                I24::unchecked_from((self.next_u64() as i32) >> 8)
+            }
+
+            fn bytes(&mut self) -> Bytes {
+                (0..self.below(70)).map(|_| self.next_u64() as u8).collect()
+            }
+
+            fn pool_key(&mut self) -> PoolKey {
+                PoolKey {
+                    currency0: self.address(),
+                    currency1: self.address(),
+                    fee: U24::from(self.next_u64() & 0xff_ffff),
+                    tickSpacing: self.tick(),
+                    hooks: self.address(),
+                }
+            }
+
+            fn path(&mut self) -> Vec<PathKey> {
+                (0..self.below(4))
+                    .map(|_| PathKey {
+                        intermediateCurrency: self.address(),
+                        fee: self.u256(),
+                        tickSpacing: self.tick(),
+                        hooks: self.address(),
+                        hookData: self.bytes(),
+                    })
+                    .collect()
+            }
+
+            fn action(&mut self) -> Actions {
+                match self.below(NUM_ACTIONS as u64) {
+                    0 => Actions::INCREASE_LIQUIDITY(IncreaseLiquidityParams {
+                        tokenId: self.u256(),
+                        liquidity: self.u256(),
+                        amount0Max: self.u128(),
+                        amount1Max: self.u128(),
+                        hookData: self.bytes(),
+                    }),
+                    1 => Actions::DECREASE_LIQUIDITY(DecreaseLiquidityParams {
+                        tokenId: self.u256(),
+                        liquidity: self.u256(),
+                        amount0Min: self.u128(),
+                        amount1Min: self.u128(),
+                        hookData: self.bytes(),
+                    }),
+                    2 => Actions::MINT_POSITION(MintPositionParams {
+                        poolKey: self.pool_key(),
+                        tickLower: self.tick(),
+                        tickUpper: self.tick(),
+                        liquidity: self.u256(),
+                        amount0Max: self.u128(),
+                        amount1Max: self.u128(),
+                        owner: self.address(),
+                        hookData: self.bytes(),
+                    }),
+                    3 => Actions::BURN_POSITION(BurnPositionParams {
+                        tokenId: self.u256(),
+                        amount0Min: self.u128(),
+                        amount1Min: self.u128(),
+                        hookData: self.bytes(),
+                    }),
+                    4 => Actions::MINT_POSITION_FROM_DELTAS(MintPositionFromDeltasParams {
+                        poolKey: self.pool_key(),
+                        tickLower: self.tick(),
+                        tickUpper: self.tick(),
+                        amount0Max: self.u128(),
+                        amount1Max: self.u128(),
+                        owner: self.address(),
+                        hookData: self.bytes(),
+                    }),
+                    5 => Actions::SWAP_EXACT_IN_SINGLE(SwapExactInSingleParams {
+                        poolKey: self.pool_key(),
+                        zeroForOne: self.bool(),
+                        amountIn: self.u128(),
+                        amountOutMinimum: self.u128(),
+                        sqrtPriceLimitX96: self.u160(),
+                        hookData: self.bytes(),
+                    }),
+                    6 => Actions::SWAP_EXACT_IN(SwapExactInParams {
+                        currencyIn: self.address(),
+                        path: self.path(),
+                        amountIn: self.u128(),
+                        amountOutMinimum: self.u128(),
+                    }),
+                    7 => Actions::SWAP_EXACT_OUT_SINGLE(SwapExactOutSingleParams {
+                        poolKey: self.pool_key(),
+                        zeroForOne: self.bool(),
+                        amountOut: self.u128(),
+                        amountInMaximum: self.u128(),
+                        sqrtPriceLimitX96: self.u160(),
+                        hookData: self.bytes(),
+                    }),
+                    8 => Actions::SWAP_EXACT_OUT(SwapExactOutParams {
+                        currencyOut: self.address(),
+                        path: self.path(),
+                        amountOut: self.u128(),
+                        amountInMaximum: self.u128(),
+                    }),
+                    9 => Actions::SETTLE(SettleParams {
+                        currency: self.address(),
+                        amount: self.u256(),
+                        payerIsUser: self.bool(),
+                    }),
+                    10 => Actions::SETTLE_ALL(SettleAllParams {
+                        currency: self.address(),
+                        maxAmount: self.u256(),
+                    }),
+                    11 => Actions::SETTLE_PAIR(SettlePairParams {
+                        currency0: self.address(),
+                        currency1: self.address(),
+                    }),
+                    12 => Actions::TAKE(TakeParams {
+                        currency: self.address(),
+                        recipient: self.address(),
+                        amount: self.u256(),
+                    }),
+                    13 => Actions::TAKE_ALL(TakeAllParams {
+                        currency: self.address(),
+                        minAmount: self.u256(),
+                    }),
+                    14 => Actions::TAKE_PORTION(TakePortionParams {
+                        currency: self.address(),
+                        recipient: self.address(),
+                        bips: self.u256(),
+                    }),
+                    15 => Actions::TAKE_PAIR(TakePairParams {
+                        currency0: self.address(),
+                        currency1: self.address(),
+                        recipient: self.address(),
+                    }),
+                    16 => Actions::CLOSE_CURRENCY(CloseCurrencyParams {
+                        currency: self.address(),
+                    }),
+                    _ => Actions::SWEEP(SweepParams {
+                        currency: self.address(),
+                        recipient: self.address(),
+                    }),
+                }
+            }
+        }
+
+        #[test]
+        fn random_action_sequences_round_trip() {
+            let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+            let mut commands = rustc_hash::FxHashSet::default();
+            for _ in 0..256 {
+                let actions: Vec<Actions> = (0..rng.below(8)).map(|_| rng.action()).collect();
+                let mut planner = V4Planner::default();
+                for action in &actions {
+                    planner.add_action(action);
+                    commands.insert(action.command());
+                }
+                let calldata = planner.finalize();
+                assert_eq!(parse_calldata(&calldata).unwrap().actions, actions);
+            }
+            // every variant was generated, so a new variant must be added to the generator
+            assert_eq!(commands.len(), NUM_ACTIONS);
+        }
+    }
 }
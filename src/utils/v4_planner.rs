@@ -1,38 +1,76 @@
 use crate::prelude::{encode_route_to_path, Error, Trade, *};
-use alloy_primitives::{Bytes, U256};
-use alloy_sol_types::SolValue;
+use alloy_primitives::{Bytes, U160, U256};
+use alloy_sol_types::{SolType, SolValue};
 use uniswap_sdk_core::prelude::*;
 use uniswap_v3_sdk::prelude::*;
 
+/// Opcodes of the actions supported by the v4 router and position manager, as defined in the
+/// periphery `Actions` library. Only the opcodes with an [`Actions`] variant can be planned.
+pub mod opcodes {
+    // Liquidity actions
+    pub const INCREASE_LIQUIDITY: u8 = 0x00;
+    pub const DECREASE_LIQUIDITY: u8 = 0x01;
+    pub const MINT_POSITION: u8 = 0x02;
+    pub const BURN_POSITION: u8 = 0x03;
+    pub const INCREASE_LIQUIDITY_FROM_DELTAS: u8 = 0x04;
+    pub const MINT_POSITION_FROM_DELTAS: u8 = 0x05;
+    // Swapping
+    pub const SWAP_EXACT_IN_SINGLE: u8 = 0x06;
+    pub const SWAP_EXACT_IN: u8 = 0x07;
+    pub const SWAP_EXACT_OUT_SINGLE: u8 = 0x08;
+    pub const SWAP_EXACT_OUT: u8 = 0x09;
+    // Donate
+    pub const DONATE: u8 = 0x0a;
+    // Settling
+    pub const SETTLE: u8 = 0x0b;
+    pub const SETTLE_ALL: u8 = 0x0c;
+    pub const SETTLE_PAIR: u8 = 0x0d;
+    // Taking
+    pub const TAKE: u8 = 0x0e;
+    pub const TAKE_ALL: u8 = 0x0f;
+    pub const TAKE_PORTION: u8 = 0x10;
+    pub const TAKE_PAIR: u8 = 0x11;
+
+    pub const CLOSE_CURRENCY: u8 = 0x12;
+    pub const CLEAR_OR_TAKE: u8 = 0x13;
+    pub const SWEEP: u8 = 0x14;
+    pub const WRAP: u8 = 0x15;
+    pub const UNWRAP: u8 = 0x16;
+    // Minting/burning 6909s to close deltas
+    pub const MINT_6909: u8 = 0x17;
+    pub const BURN_6909: u8 = 0x18;
+}
+
 #[allow(non_camel_case_types)]
 #[derive(Clone, Debug, PartialEq)]
 #[repr(u8)]
 pub enum Actions {
     // Pool actions
     // Liquidity actions
-    INCREASE_LIQUIDITY(IncreaseLiquidityParams) = 0x00,
-    DECREASE_LIQUIDITY(DecreaseLiquidityParams) = 0x01,
-    MINT_POSITION(MintPositionParams) = 0x02,
-    BURN_POSITION(BurnPositionParams) = 0x03,
+    INCREASE_LIQUIDITY(IncreaseLiquidityParams) = opcodes::INCREASE_LIQUIDITY,
+    DECREASE_LIQUIDITY(DecreaseLiquidityParams) = opcodes::DECREASE_LIQUIDITY,
+    MINT_POSITION(MintPositionParams) = opcodes::MINT_POSITION,
+    BURN_POSITION(BurnPositionParams) = opcodes::BURN_POSITION,
+    MINT_POSITION_FROM_DELTAS(MintPositionFromDeltasParams) = opcodes::MINT_POSITION_FROM_DELTAS,
     // Swapping
-    SWAP_EXACT_IN_SINGLE(SwapExactInSingleParams) = 0x06,
-    SWAP_EXACT_IN(SwapExactInParams) = 0x07,
-    SWAP_EXACT_OUT_SINGLE(SwapExactOutSingleParams) = 0x08,
-    SWAP_EXACT_OUT(SwapExactOutParams) = 0x09,
+    SWAP_EXACT_IN_SINGLE(SwapExactInSingleParams) = opcodes::SWAP_EXACT_IN_SINGLE,
+    SWAP_EXACT_IN(SwapExactInParams) = opcodes::SWAP_EXACT_IN,
+    SWAP_EXACT_OUT_SINGLE(SwapExactOutSingleParams) = opcodes::SWAP_EXACT_OUT_SINGLE,
+    SWAP_EXACT_OUT(SwapExactOutParams) = opcodes::SWAP_EXACT_OUT,
 
     // Closing deltas on the pool manager
     // Settling
-    SETTLE(SettleParams) = 0x0b,
-    SETTLE_ALL(SettleAllParams) = 0x0c,
-    SETTLE_PAIR(SettlePairParams) = 0x0d,
+    SETTLE(SettleParams) = opcodes::SETTLE,
+    SETTLE_ALL(SettleAllParams) = opcodes::SETTLE_ALL,
+    SETTLE_PAIR(SettlePairParams) = opcodes::SETTLE_PAIR,
     // Taking
-    TAKE(TakeParams) = 0x0e,
-    TAKE_ALL(TakeAllParams) = 0x0f,
-    TAKE_PORTION(TakePortionParams) = 0x10,
-    TAKE_PAIR(TakePairParams) = 0x11,
+    TAKE(TakeParams) = opcodes::TAKE,
+    TAKE_ALL(TakeAllParams) = opcodes::TAKE_ALL,
+    TAKE_PORTION(TakePortionParams) = opcodes::TAKE_PORTION,
+    TAKE_PAIR(TakePairParams) = opcodes::TAKE_PAIR,
 
-    CLOSE_CURRENCY(CloseCurrencyParams) = 0x12,
-    SWEEP(SweepParams) = 0x14,
+    CLOSE_CURRENCY(CloseCurrencyParams) = opcodes::CLOSE_CURRENCY,
+    SWEEP(SweepParams) = opcodes::SWEEP,
 }
 
 /// https://doc.rust-lang.org/error_codes/E0732.html
@@ -54,6 +92,7 @@ impl Actions {
             Self::DECREASE_LIQUIDITY(params) => params.abi_encode(),
             Self::MINT_POSITION(params) => params.abi_encode(),
             Self::BURN_POSITION(params) => params.abi_encode(),
+            Self::MINT_POSITION_FROM_DELTAS(params) => params.abi_encode(),
             Self::SWAP_EXACT_IN_SINGLE(params) => params.abi_encode(),
             Self::SWAP_EXACT_IN(params) => params.abi_encode(),
             Self::SWAP_EXACT_OUT_SINGLE(params) => params.abi_encode(),
@@ -71,32 +110,57 @@ impl Actions {
         .into()
     }
 
+    /// Decodes the params of the action with the given command
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::InvalidAction`] if the command is not supported, and
+    /// [`Error::MalformedActionParams`] if the params are not the exact encoding of the command's
+    /// params.
     #[inline]
     pub fn abi_decode(command: u8, data: &Bytes) -> Result<Self, Error> {
         let data = data.iter().as_slice();
         Ok(match command {
-            0x00 => Self::INCREASE_LIQUIDITY(IncreaseLiquidityParams::abi_decode(data, true)?),
-            0x01 => Self::DECREASE_LIQUIDITY(DecreaseLiquidityParams::abi_decode(data, true)?),
-            0x02 => Self::MINT_POSITION(MintPositionParams::abi_decode(data, true)?),
-            0x03 => Self::BURN_POSITION(BurnPositionParams::abi_decode(data, true)?),
-            0x06 => Self::SWAP_EXACT_IN_SINGLE(SwapExactInSingleParams::abi_decode(data, true)?),
-            0x07 => Self::SWAP_EXACT_IN(SwapExactInParams::abi_decode(data, true)?),
-            0x08 => Self::SWAP_EXACT_OUT_SINGLE(SwapExactOutSingleParams::abi_decode(data, true)?),
-            0x09 => Self::SWAP_EXACT_OUT(SwapExactOutParams::abi_decode(data, true)?),
-            0x0b => Self::SETTLE(SettleParams::abi_decode(data, true)?),
-            0x0c => Self::SETTLE_ALL(SettleAllParams::abi_decode(data, true)?),
-            0x0d => Self::SETTLE_PAIR(SettlePairParams::abi_decode(data, true)?),
-            0x0e => Self::TAKE(TakeParams::abi_decode(data, true)?),
-            0x0f => Self::TAKE_ALL(TakeAllParams::abi_decode(data, true)?),
-            0x10 => Self::TAKE_PORTION(TakePortionParams::abi_decode(data, true)?),
-            0x11 => Self::TAKE_PAIR(TakePairParams::abi_decode(data, true)?),
-            0x12 => Self::CLOSE_CURRENCY(CloseCurrencyParams::abi_decode(data, true)?),
-            0x14 => Self::SWEEP(SweepParams::abi_decode(data, true)?),
+            opcodes::INCREASE_LIQUIDITY => Self::INCREASE_LIQUIDITY(decode_params(command, data)?),
+            opcodes::DECREASE_LIQUIDITY => Self::DECREASE_LIQUIDITY(decode_params(command, data)?),
+            opcodes::MINT_POSITION => Self::MINT_POSITION(decode_params(command, data)?),
+            opcodes::BURN_POSITION => Self::BURN_POSITION(decode_params(command, data)?),
+            opcodes::MINT_POSITION_FROM_DELTAS => {
+                Self::MINT_POSITION_FROM_DELTAS(decode_params(command, data)?)
+            }
+            opcodes::SWAP_EXACT_IN_SINGLE => {
+                Self::SWAP_EXACT_IN_SINGLE(decode_params(command, data)?)
+            }
+            opcodes::SWAP_EXACT_IN => Self::SWAP_EXACT_IN(decode_params(command, data)?),
+            opcodes::SWAP_EXACT_OUT_SINGLE => {
+                Self::SWAP_EXACT_OUT_SINGLE(decode_params(command, data)?)
+            }
+            opcodes::SWAP_EXACT_OUT => Self::SWAP_EXACT_OUT(decode_params(command, data)?),
+            opcodes::SETTLE => Self::SETTLE(decode_params(command, data)?),
+            opcodes::SETTLE_ALL => Self::SETTLE_ALL(decode_params(command, data)?),
+            opcodes::SETTLE_PAIR => Self::SETTLE_PAIR(decode_params(command, data)?),
+            opcodes::TAKE => Self::TAKE(decode_params(command, data)?),
+            opcodes::TAKE_ALL => Self::TAKE_ALL(decode_params(command, data)?),
+            opcodes::TAKE_PORTION => Self::TAKE_PORTION(decode_params(command, data)?),
+            opcodes::TAKE_PAIR => Self::TAKE_PAIR(decode_params(command, data)?),
+            opcodes::CLOSE_CURRENCY => Self::CLOSE_CURRENCY(decode_params(command, data)?),
+            opcodes::SWEEP => Self::SWEEP(decode_params(command, data)?),
             _ => return Err(Error::InvalidAction(command)),
         })
     }
 }
 
+/// Decodes action params, rejecting truncated params as well as trailing or non-canonical bytes
+fn decode_params<T>(opcode: u8, data: &[u8]) -> Result<T, Error>
+where
+    T: SolValue + From<<T::SolType as SolType>::RustType>,
+{
+    T::abi_decode(data, true)
+        .ok()
+        .filter(|params| params.abi_encode() == data)
+        .ok_or(Error::MalformedActionParams { opcode })
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct V4Planner {
     pub actions: Vec<u8>,
@@ -138,9 +202,9 @@ impl V4Planner {
         );
 
         let route = trade.route();
-        let currency_in = currency_address(&route.path_input);
-        let currency_out = currency_address(&route.path_output);
-        let path = encode_route_to_path(route, exact_output);
+        let currency_in = to_address(&route.path_input);
+        let currency_out = to_address(&route.path_output);
+        let path = encode_route_to_path(route, exact_output)?;
 
         Ok(self.add_action(
             &(if exact_output {
@@ -173,6 +237,126 @@ impl V4Planner {
         ))
     }
 
+    /// Adds an exact input trade of one or more swaps with an aggregated slippage check.
+    ///
+    /// Each swap is encoded as a `SWAP_EXACT_IN` with an `amountOutMinimum` of zero, followed by
+    /// a single `TAKE_ALL` of the output currency whose `minAmount` is the minimum amount out of
+    /// the whole trade, so that slippage is enforced on the total rather than per route. The input
+    /// currency still needs to be settled.
+    ///
+    /// ## Arguments
+    ///
+    /// * `trade`: The exact input trade to add
+    /// * `slippage_tolerance`: The slippage tolerance of the aggregated output
+    #[inline]
+    pub fn add_trades<TInput, TOutput, TP>(
+        &mut self,
+        trade: &Trade<TInput, TOutput, TP>,
+        slippage_tolerance: Percent,
+    ) -> Result<&mut Self, Error>
+    where
+        TInput: BaseCurrency,
+        TOutput: BaseCurrency,
+        TP: TickDataProvider,
+    {
+        assert_eq!(
+            trade.trade_type,
+            TradeType::ExactInput,
+            "Aggregated slippage checks require an exact input trade"
+        );
+        for Swap {
+            route,
+            input_amount,
+            ..
+        } in &trade.swaps
+        {
+            self.add_action(&Actions::SWAP_EXACT_IN(SwapExactInParams {
+                currencyIn: to_address(&route.path_input),
+                path: encode_route_to_path(route, false)?,
+                amountIn: input_amount.quotient().to_u128().unwrap(),
+                amountOutMinimum: 0,
+            }));
+        }
+        let minimum_amount_out = trade.minimum_amount_out(slippage_tolerance, None)?;
+        Ok(self.add_action(&Actions::TAKE_ALL(TakeAllParams {
+            currency: to_address(&trade.swaps[0].route.path_output),
+            minAmount: U256::from_big_int(minimum_amount_out.quotient()),
+        })))
+    }
+
+    /// Adds a trade as a single-pool swap carrying a price limit.
+    ///
+    /// Emits `SWAP_EXACT_IN_SINGLE` or `SWAP_EXACT_OUT_SINGLE` when the trade's route goes through
+    /// a single pool. Multi-hop routes fall back to [`V4Planner::add_trade`], since path swaps do
+    /// not support a price limit.
+    ///
+    /// ## Arguments
+    ///
+    /// * `trade`: The trade to add
+    /// * `slippage_tolerance`: The slippage tolerance, required for exact output trades
+    /// * `sqrt_price_limit_x96`: The Q64.96 sqrt price limit of the swap
+    #[inline]
+    pub fn add_single_hop_trade<TInput, TOutput, TP>(
+        &mut self,
+        trade: &Trade<TInput, TOutput, TP>,
+        slippage_tolerance: Option<Percent>,
+        sqrt_price_limit_x96: U160,
+    ) -> Result<&mut Self, Error>
+    where
+        TInput: BaseCurrency,
+        TOutput: BaseCurrency,
+        TP: TickDataProvider,
+    {
+        if trade.swaps.len() != 1 || trade.route().pools.len() != 1 {
+            return self.add_trade(trade, slippage_tolerance);
+        }
+        let exact_output = trade.trade_type == TradeType::ExactOutput;
+        if exact_output {
+            assert!(
+                slippage_tolerance.is_some(),
+                "ExactOut requires slippageTolerance"
+            );
+        }
+
+        let route = trade.route();
+        let pool = &route.pools[0];
+        let zero_for_one = route.path_input.equals(&pool.currency0);
+
+        Ok(self.add_action(
+            &(if exact_output {
+                Actions::SWAP_EXACT_OUT_SINGLE(SwapExactOutSingleParams {
+                    poolKey: pool.pool_key.clone(),
+                    zeroForOne: zero_for_one,
+                    amountOut: trade.output_amount()?.quotient().to_u128().unwrap(),
+                    amountInMaximum: trade
+                        .maximum_amount_in(slippage_tolerance.unwrap_or_default(), None)?
+                        .quotient()
+                        .to_u128()
+                        .unwrap(),
+                    sqrtPriceLimitX96: sqrt_price_limit_x96,
+                    hookData: Bytes::default(),
+                })
+            } else {
+                Actions::SWAP_EXACT_IN_SINGLE(SwapExactInSingleParams {
+                    poolKey: pool.pool_key.clone(),
+                    zeroForOne: zero_for_one,
+                    amountIn: trade.input_amount()?.quotient().to_u128().unwrap(),
+                    amountOutMinimum: if let Some(slippage_tolerance) = slippage_tolerance {
+                        trade
+                            .minimum_amount_out(slippage_tolerance, None)?
+                            .quotient()
+                            .to_u128()
+                            .unwrap()
+                    } else {
+                        0
+                    },
+                    sqrtPriceLimitX96: sqrt_price_limit_x96,
+                    hookData: Bytes::default(),
+                })
+            }),
+        ))
+    }
+
     #[inline]
     pub fn add_settle(
         &mut self,
@@ -181,12 +365,29 @@ impl V4Planner {
         amount: Option<U256>,
     ) -> &mut Self {
         self.add_action(&Actions::SETTLE(SettleParams {
-            currency: currency_address(currency),
+            currency: to_address(currency),
             amount: amount.unwrap_or_default(),
             payerIsUser: payer_is_user,
         }))
     }
 
+    /// Settles the entire balance the contract holds of `currency`, by passing the
+    /// [`CONTRACT_BALANCE`] amount sentinel
+    ///
+    /// ## Arguments
+    ///
+    /// * `currency`: The currency to settle
+    /// * `payer_is_user`: Whether the funds are pulled from the user rather than the contract;
+    ///   normally `false`, as the sentinel refers to the contract's own balance
+    #[inline]
+    pub fn add_settle_contract_balance(
+        &mut self,
+        currency: &impl BaseCurrency,
+        payer_is_user: bool,
+    ) -> &mut Self {
+        self.add_settle(currency, payer_is_user, Some(CONTRACT_BALANCE))
+    }
+
     #[inline]
     pub fn add_take(
         &mut self,
@@ -195,12 +396,25 @@ impl V4Planner {
         amount: Option<U256>,
     ) -> &mut Self {
         self.add_action(&Actions::TAKE(TakeParams {
-            currency: currency_address(currency),
+            currency: to_address(currency),
             recipient,
             amount: amount.unwrap_or_default(),
         }))
     }
 
+    /// Appends the actions and params of another planner, in order, after the ones already
+    /// queued, e.g. to reuse a sub-planner across several flows
+    ///
+    /// ## Arguments
+    ///
+    /// * `other`: The planner whose actions are appended
+    #[inline]
+    pub fn extend(&mut self, other: Self) -> &mut Self {
+        self.actions.extend(other.actions);
+        self.params.extend(other.params);
+        self
+    }
+
     #[inline]
     #[must_use]
     pub fn finalize(self) -> Bytes {
@@ -211,13 +425,18 @@ impl V4Planner {
         .abi_encode()
         .into()
     }
-}
 
-fn currency_address(currency: &impl BaseCurrency) -> Address {
-    if currency.is_native() {
-        Address::ZERO
-    } else {
-        currency.address()
+    /// Finalizes the planner like [`Self::finalize`], but rejects a planner with no actions
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::EmptyPlanner`] if no actions have been added.
+    #[inline]
+    pub fn try_finalize(self) -> Result<Bytes, Error> {
+        if self.actions.is_empty() {
+            return Err(Error::EmptyPlanner);
+        }
+        Ok(self.finalize())
     }
 }
 
@@ -228,7 +447,7 @@ mod tests {
         prelude::{Pool, Route},
         tests::*,
     };
-    use alloy_primitives::{hex, U160};
+    use alloy_primitives::hex;
     use once_cell::sync::Lazy;
 
     static USDC_WETH: Lazy<Pool<Vec<Tick>>> = Lazy::new(|| {
@@ -289,6 +508,10 @@ mod tests {
             discriminant(&Actions::BURN_POSITION(Default::default())),
             0x03
         );
+        assert_eq!(
+            discriminant(&Actions::MINT_POSITION_FROM_DELTAS(Default::default())),
+            0x05
+        );
         assert_eq!(
             discriminant(&Actions::SWAP_EXACT_IN_SINGLE(Default::default())),
             0x06
@@ -359,7 +582,7 @@ mod tests {
             let mut planner = V4Planner::default();
             planner.add_action(&Actions::SWAP_EXACT_IN(SwapExactInParams {
                 currencyIn: DAI.address,
-                path: encode_route_to_path(&route, false),
+                path: encode_route_to_path(&route, false).unwrap(),
                 amountIn: ONE_ETHER,
                 amountOutMinimum: 0,
             }));
@@ -383,6 +606,45 @@ mod tests {
             assert_eq!(planner.params[0], trade_planner.params[0]);
         }
 
+        #[test]
+        fn encodes_minimum_out_of_fee_on_transfer_trade() {
+            let route = Route::new(
+                vec![DAI_USDC.clone(), USDC_WETH.clone()],
+                DAI.clone(),
+                WETH.clone(),
+            )
+            .unwrap();
+            let slippage_tolerance = Percent::new(5, 100);
+            let trade = Trade::from_route(
+                route.clone(),
+                CurrencyAmount::from_raw_amount(DAI.clone(), ONE_ETHER).unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap()
+            .with_fee_on_transfer_support(Percent::new(3, 100))
+            .unwrap();
+            let minimum_amount_out = trade
+                .minimum_amount_out(slippage_tolerance.clone(), None)
+                .unwrap()
+                .quotient()
+                .to_u128()
+                .unwrap();
+            assert!(minimum_amount_out > 0);
+
+            let mut planner = V4Planner::default();
+            planner.add_trade(&trade, Some(slippage_tolerance)).unwrap();
+
+            let mut expected = V4Planner::default();
+            expected.add_action(&Actions::SWAP_EXACT_IN(SwapExactInParams {
+                currencyIn: DAI.address,
+                path: encode_route_to_path(&route, false).unwrap(),
+                amountIn: ONE_ETHER,
+                amountOutMinimum: minimum_amount_out,
+            }));
+            assert_eq!(planner.actions, expected.actions);
+            assert_eq!(planner.params, expected.params);
+        }
+
         #[test]
         fn completes_v4_exact_out_2_hop_swap() {
             let route = Route::new(
@@ -501,6 +763,165 @@ mod tests {
         }
     }
 
+    mod add_trades {
+        use super::*;
+
+        #[test]
+        fn enforces_aggregated_minimum_on_take_all() {
+            let slippage_tolerance = Percent::new(5, 100);
+            let trade = Trade::from_routes(
+                vec![
+                    (
+                        CurrencyAmount::from_raw_amount(USDC.clone(), 1_000_000).unwrap(),
+                        Route::new(vec![USDC_WETH.clone()], USDC.clone(), WETH.clone()).unwrap(),
+                    ),
+                    (
+                        CurrencyAmount::from_raw_amount(USDC.clone(), 3_000_000).unwrap(),
+                        Route::new(
+                            vec![DAI_USDC.clone(), DAI_WETH.clone()],
+                            USDC.clone(),
+                            WETH.clone(),
+                        )
+                        .unwrap(),
+                    ),
+                ],
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            let mut planner = V4Planner::default();
+            planner
+                .add_trades(&trade, slippage_tolerance.clone())
+                .unwrap();
+            assert_eq!(planner.actions, vec![0x07, 0x07, 0x0f]);
+
+            let actions = parse_calldata(&planner.finalize()).unwrap().actions;
+            for (action, swap) in actions.iter().zip(&trade.swaps) {
+                let Actions::SWAP_EXACT_IN(params) = action else {
+                    panic!("expected SWAP_EXACT_IN");
+                };
+                assert_eq!(params.currencyIn, USDC.address);
+                assert_eq!(
+                    params.amountIn,
+                    swap.input_amount.quotient().to_u128().unwrap()
+                );
+                assert_eq!(params.amountOutMinimum, 0);
+            }
+            let minimum_amount_out = trade
+                .minimum_amount_out(slippage_tolerance, None)
+                .unwrap()
+                .quotient();
+            assert!(minimum_amount_out > BigInt::from(0));
+            assert_eq!(
+                actions[2],
+                Actions::TAKE_ALL(TakeAllParams {
+                    currency: WETH.address,
+                    minAmount: U256::from_big_int(minimum_amount_out),
+                })
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Aggregated slippage checks require an exact input trade")]
+        fn throws_for_exact_output() {
+            let trade = Trade::from_route(
+                Route::new(vec![USDC_WETH.clone()], USDC.clone(), WETH.clone()).unwrap(),
+                CurrencyAmount::from_raw_amount(WETH.clone(), 1_000_000).unwrap(),
+                TradeType::ExactOutput,
+            )
+            .unwrap();
+            let _ = V4Planner::default().add_trades(&trade, Percent::new(5, 100));
+        }
+    }
+
+    mod add_single_hop_trade {
+        use super::*;
+
+        #[test]
+        fn uses_single_action_with_price_limit_for_exact_in() {
+            let route = Route::new(vec![USDC_WETH.clone()], USDC.clone(), WETH.clone()).unwrap();
+            let trade = Trade::from_route(
+                route,
+                CurrencyAmount::from_raw_amount(USDC.clone(), ONE_ETHER).unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            let sqrt_price_limit_x96 = encode_sqrt_ratio_x96(1, 2);
+            let mut planner = V4Planner::default();
+            planner
+                .add_single_hop_trade(&trade, None, sqrt_price_limit_x96)
+                .unwrap();
+
+            assert_eq!(planner.actions, vec![0x06]);
+            let params = SwapExactInSingleParams::abi_decode(&planner.params[0], true).unwrap();
+            assert_eq!(params.poolKey, USDC_WETH.pool_key);
+            assert!(params.zeroForOne);
+            assert_eq!(params.amountIn, ONE_ETHER);
+            assert_eq!(params.amountOutMinimum, 0);
+            assert_eq!(params.sqrtPriceLimitX96, sqrt_price_limit_x96);
+        }
+
+        #[test]
+        fn uses_single_action_with_price_limit_for_exact_out() {
+            let route = Route::new(vec![USDC_WETH.clone()], WETH.clone(), USDC.clone()).unwrap();
+            let trade = Trade::from_route(
+                route,
+                CurrencyAmount::from_raw_amount(USDC.clone(), ONE_ETHER).unwrap(),
+                TradeType::ExactOutput,
+            )
+            .unwrap();
+            let sqrt_price_limit_x96 = encode_sqrt_ratio_x96(2, 1);
+            let slippage_tolerance = Percent::new(5, 100);
+            let mut planner = V4Planner::default();
+            planner
+                .add_single_hop_trade(
+                    &trade,
+                    Some(slippage_tolerance.clone()),
+                    sqrt_price_limit_x96,
+                )
+                .unwrap();
+
+            assert_eq!(planner.actions, vec![0x08]);
+            let params = SwapExactOutSingleParams::abi_decode(&planner.params[0], true).unwrap();
+            assert_eq!(params.poolKey, USDC_WETH.pool_key);
+            assert!(!params.zeroForOne);
+            assert_eq!(params.amountOut, ONE_ETHER);
+            assert_eq!(
+                params.amountInMaximum,
+                trade
+                    .maximum_amount_in(slippage_tolerance, None)
+                    .unwrap()
+                    .quotient()
+                    .to_u128()
+                    .unwrap()
+            );
+            assert_eq!(params.sqrtPriceLimitX96, sqrt_price_limit_x96);
+        }
+
+        #[test]
+        fn falls_back_to_path_swap_for_multi_hop_route() {
+            let route = Route::new(
+                vec![DAI_USDC.clone(), USDC_WETH.clone()],
+                DAI.clone(),
+                WETH.clone(),
+            )
+            .unwrap();
+            let trade = Trade::from_route(
+                route,
+                CurrencyAmount::from_raw_amount(DAI.clone(), ONE_ETHER).unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            let mut planner = V4Planner::default();
+            planner
+                .add_single_hop_trade(&trade, None, encode_sqrt_ratio_x96(1, 2))
+                .unwrap();
+            let mut trade_planner = V4Planner::default();
+            trade_planner.add_trade(&trade, None).unwrap();
+
+            assert_eq!(planner, trade_planner);
+        }
+    }
+
     mod add_settle {
         use super::*;
         use alloy_primitives::uint;
@@ -539,6 +960,28 @@ mod tests {
         }
     }
 
+    mod add_settle_contract_balance {
+        use super::*;
+
+        #[test]
+        fn encodes_the_contract_balance_sentinel() {
+            let mut planner = V4Planner::default();
+            planner.add_settle_contract_balance(&DAI.clone(), false);
+            assert_eq!(planner.actions, vec![0x0b]);
+            assert_eq!(
+                planner.params[0],
+                hex!("0000000000000000000000006b175474e89094c44da98b954eedeac495271d0f80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000").to_vec()
+            );
+            let Actions::SETTLE(params) =
+                Actions::abi_decode(planner.actions[0], &planner.params[0]).unwrap()
+            else {
+                panic!("expected SETTLE");
+            };
+            assert_eq!(params.amount, CONTRACT_BALANCE);
+            assert!(!params.payerIsUser);
+        }
+    }
+
     mod add_take {
         use super::*;
         use alloy_primitives::uint;
@@ -573,4 +1016,144 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn settle_and_take_map_native_currency_to_zero_address() {
+        let mut planner = V4Planner::default();
+        planner.add_settle(&ETHER.clone(), true, None);
+        planner.add_take(&ETHER.clone(), MSG_SENDER, None);
+        assert_eq!(
+            parse_calldata(&planner.finalize()).unwrap().actions,
+            vec![
+                Actions::SETTLE(SettleParams {
+                    currency: Address::ZERO,
+                    amount: U256::ZERO,
+                    payerIsUser: true,
+                }),
+                Actions::TAKE(TakeParams {
+                    currency: Address::ZERO,
+                    recipient: MSG_SENDER,
+                    amount: U256::ZERO,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn commands_match_opcodes() {
+        let actions = [
+            (
+                Actions::INCREASE_LIQUIDITY(Default::default()),
+                opcodes::INCREASE_LIQUIDITY,
+            ),
+            (
+                Actions::DECREASE_LIQUIDITY(Default::default()),
+                opcodes::DECREASE_LIQUIDITY,
+            ),
+            (
+                Actions::MINT_POSITION(Default::default()),
+                opcodes::MINT_POSITION,
+            ),
+            (
+                Actions::BURN_POSITION(Default::default()),
+                opcodes::BURN_POSITION,
+            ),
+            (
+                Actions::MINT_POSITION_FROM_DELTAS(Default::default()),
+                opcodes::MINT_POSITION_FROM_DELTAS,
+            ),
+            (
+                Actions::SWAP_EXACT_IN_SINGLE(Default::default()),
+                opcodes::SWAP_EXACT_IN_SINGLE,
+            ),
+            (
+                Actions::SWAP_EXACT_IN(Default::default()),
+                opcodes::SWAP_EXACT_IN,
+            ),
+            (
+                Actions::SWAP_EXACT_OUT_SINGLE(Default::default()),
+                opcodes::SWAP_EXACT_OUT_SINGLE,
+            ),
+            (
+                Actions::SWAP_EXACT_OUT(Default::default()),
+                opcodes::SWAP_EXACT_OUT,
+            ),
+            (Actions::SETTLE(Default::default()), opcodes::SETTLE),
+            (Actions::SETTLE_ALL(Default::default()), opcodes::SETTLE_ALL),
+            (
+                Actions::SETTLE_PAIR(Default::default()),
+                opcodes::SETTLE_PAIR,
+            ),
+            (Actions::TAKE(Default::default()), opcodes::TAKE),
+            (Actions::TAKE_ALL(Default::default()), opcodes::TAKE_ALL),
+            (
+                Actions::TAKE_PORTION(Default::default()),
+                opcodes::TAKE_PORTION,
+            ),
+            (Actions::TAKE_PAIR(Default::default()), opcodes::TAKE_PAIR),
+            (
+                Actions::CLOSE_CURRENCY(Default::default()),
+                opcodes::CLOSE_CURRENCY,
+            ),
+            (Actions::SWEEP(Default::default()), opcodes::SWEEP),
+        ];
+        for (action, opcode) in actions {
+            assert_eq!(action.command(), opcode);
+            let decoded = Actions::abi_decode(opcode, &action.abi_encode()).unwrap();
+            assert_eq!(decoded, action);
+        }
+    }
+
+    mod extend {
+        use super::*;
+
+        #[test]
+        fn composes_planners_in_order() {
+            let mut inner = V4Planner::default();
+            inner
+                .add_settle(&ETHER.clone(), true, None)
+                .add_take(&DAI.clone(), MSG_SENDER, None);
+            let mut outer = V4Planner::default();
+            outer.add_settle(&USDC.clone(), true, None);
+            outer.extend(inner);
+            outer.add_take(&USDC.clone(), MSG_SENDER, None);
+
+            let mut expected = V4Planner::default();
+            expected
+                .add_settle(&USDC.clone(), true, None)
+                .add_settle(&ETHER.clone(), true, None)
+                .add_take(&DAI.clone(), MSG_SENDER, None)
+                .add_take(&USDC.clone(), MSG_SENDER, None);
+            assert_eq!(outer, expected);
+            assert_eq!(outer.finalize(), expected.finalize());
+        }
+
+        #[test]
+        fn extending_with_an_empty_planner_is_a_no_op() {
+            let mut planner = V4Planner::default();
+            planner.add_settle(&DAI.clone(), true, None);
+            let expected = planner.clone();
+            planner.extend(V4Planner::default());
+            assert_eq!(planner, expected);
+        }
+    }
+
+    mod try_finalize {
+        use super::*;
+
+        #[test]
+        fn errors_on_empty_planner() {
+            assert!(matches!(
+                V4Planner::default().try_finalize(),
+                Err(Error::EmptyPlanner)
+            ));
+        }
+
+        #[test]
+        fn matches_finalize_with_one_action() {
+            let mut planner = V4Planner::default();
+            planner.add_take(&DAI.clone(), Address::ZERO, None);
+            assert_eq!(planner.clone().try_finalize().unwrap(), planner.finalize());
+        }
+    }
 }
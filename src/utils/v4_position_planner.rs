@@ -1,13 +1,26 @@
-use crate::prelude::*;
+use crate::prelude::{Error, Pool, *};
 use alloy_primitives::{Address, Bytes, U256};
 use derive_more::{Deref, DerefMut};
-use uniswap_sdk_core::prelude::BaseCurrency;
-use uniswap_v3_sdk::prelude::{TickDataProvider, TickIndex};
+use uniswap_sdk_core::prelude::*;
+use uniswap_v3_sdk::prelude::*;
 
 #[derive(Clone, Debug, Default, PartialEq, Deref, DerefMut)]
 pub struct V4PositionPlanner(pub V4Planner);
 
+impl From<V4PositionPlanner> for V4Planner {
+    #[inline]
+    fn from(planner: V4PositionPlanner) -> Self {
+        planner.0
+    }
+}
+
 impl V4PositionPlanner {
+    /// Returns the inner [`V4Planner`]
+    #[inline]
+    pub const fn as_planner(&self) -> &V4Planner {
+        &self.0
+    }
+
     #[allow(clippy::too_many_arguments)]
     #[inline]
     pub fn add_mint<TP: TickDataProvider>(
@@ -33,6 +46,43 @@ impl V4PositionPlanner {
         }));
     }
 
+    /// Adds a `MINT_POSITION_FROM_DELTAS` action, which mints a position with the liquidity that
+    /// the open deltas of the pool's currencies can pay for, e.g. the output of a preceding swap
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool to mint the position in
+    /// * `tick_lower`: The lower tick of the position
+    /// * `tick_upper`: The upper tick of the position
+    /// * `amount0_max`: The maximum amount of currency0 to pay
+    /// * `amount1_max`: The maximum amount of currency1 to pay
+    /// * `owner`: The owner of the minted position
+    /// * `hook_data`: The data passed to the hooks of the pool
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn add_mint_from_deltas<TP: TickDataProvider>(
+        &mut self,
+        pool: &Pool<TP>,
+        tick_lower: TP::Index,
+        tick_upper: TP::Index,
+        amount0_max: u128,
+        amount1_max: u128,
+        owner: Address,
+        hook_data: Bytes,
+    ) {
+        self.add_action(&Actions::MINT_POSITION_FROM_DELTAS(
+            MintPositionFromDeltasParams {
+                poolKey: pool.pool_key.clone(),
+                tickLower: tick_lower.to_i24(),
+                tickUpper: tick_upper.to_i24(),
+                amount0Max: amount0_max,
+                amount1Max: amount1_max,
+                owner,
+                hookData: hook_data,
+            },
+        ));
+    }
+
     #[inline]
     pub fn add_increase(
         &mut self,
@@ -85,6 +135,69 @@ impl V4PositionPlanner {
         }));
     }
 
+    /// Compounds the fees of a position by collecting them and adding them back as liquidity.
+    ///
+    /// Encodes a `DECREASE_LIQUIDITY` of zero liquidity, which collects the fees, followed by an
+    /// `INCREASE_LIQUIDITY` of the maximum liquidity the collected amounts can provide at the
+    /// current price, and a `CLOSE_CURRENCY` for each currency to settle or take what is left.
+    ///
+    /// ## Arguments
+    ///
+    /// * `token_id`: The id of the position
+    /// * `pool`: The pool of the position
+    /// * `tick_lower`: The lower tick of the position
+    /// * `tick_upper`: The upper tick of the position
+    /// * `collected0`: The amount of fees in currency0 to compound
+    /// * `collected1`: The amount of fees in currency1 to compound
+    /// * `hook_data`: Data to pass to hooks
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`Error::LiquidityOverflow`] if the liquidity exceeds [`max_liquidity_per_tick`],
+    /// with `liquidity` saturated to [`u128::MAX`] if it does not fit in a `u128`.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn add_compound<TP: TickDataProvider>(
+        &mut self,
+        token_id: U256,
+        pool: &Pool<TP>,
+        tick_lower: TP::Index,
+        tick_upper: TP::Index,
+        collected0: u128,
+        collected1: u128,
+        hook_data: Bytes,
+    ) -> Result<(), Error> {
+        let liquidity = max_liquidity_for_amounts(
+            pool.sqrt_price_x96,
+            get_sqrt_ratio_at_tick(tick_lower.to_i24())?,
+            get_sqrt_ratio_at_tick(tick_upper.to_i24())?,
+            U256::from(collected0),
+            U256::from(collected1),
+            true,
+        );
+        let max_liquidity = max_liquidity_per_tick(pool.tick_spacing.to_i24().as_i32());
+        let liquidity = match liquidity.to_u128() {
+            Some(liquidity) if liquidity <= max_liquidity => liquidity,
+            liquidity => {
+                return Err(Error::LiquidityOverflow {
+                    liquidity: liquidity.unwrap_or(u128::MAX),
+                    max_liquidity,
+                })
+            }
+        };
+        self.add_decrease(token_id, U256::ZERO, 0, 0, hook_data.clone());
+        self.add_increase(
+            token_id,
+            U256::from(liquidity),
+            collected0,
+            collected1,
+            hook_data,
+        );
+        self.add_close_currency(&pool.currency0);
+        self.add_close_currency(&pool.currency1);
+        Ok(())
+    }
+
     #[inline]
     pub fn add_settle_pair(
         &mut self,
@@ -111,6 +224,13 @@ impl V4PositionPlanner {
         }));
     }
 
+    #[inline]
+    pub fn add_close_currency(&mut self, currency: &impl BaseCurrency) {
+        self.add_action(&Actions::CLOSE_CURRENCY(CloseCurrencyParams {
+            currency: to_address(currency),
+        }));
+    }
+
     #[inline]
     pub fn add_sweep(&mut self, currency: &impl BaseCurrency, recipient: Address) {
         self.add_action(&Actions::SWEEP(SweepParams {
@@ -119,3 +239,158 @@ impl V4PositionPlanner {
         }));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::{address, aliases::I24};
+
+    #[test]
+    fn maps_native_currency_to_zero_address() {
+        let mut planner = V4PositionPlanner::default();
+        planner.add_settle_pair(&ETHER.clone(), &USDC.clone());
+        planner.add_take_pair(&ETHER.clone(), &USDC.clone(), MSG_SENDER);
+        planner.add_sweep(&ETHER.clone(), MSG_SENDER);
+        assert_eq!(
+            parse_calldata(&planner.0.finalize()).unwrap().actions,
+            vec![
+                Actions::SETTLE_PAIR(SettlePairParams {
+                    currency0: Address::ZERO,
+                    currency1: USDC.address,
+                }),
+                Actions::TAKE_PAIR(TakePairParams {
+                    currency0: Address::ZERO,
+                    currency1: USDC.address,
+                    recipient: MSG_SENDER,
+                }),
+                Actions::SWEEP(SweepParams {
+                    currency: Address::ZERO,
+                    recipient: MSG_SENDER,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn add_mint_from_deltas_encodes_params() {
+        let owner = address!("000000000000000000000000000000000000000a");
+        let mut planner = V4PositionPlanner::default();
+        planner.add_mint_from_deltas(
+            &USDC_DAI,
+            -100,
+            100,
+            1_000_000,
+            2_000_000,
+            owner,
+            Bytes::from_static(&[0x01]),
+        );
+        assert_eq!(planner.actions, vec![0x05]);
+        assert_eq!(
+            parse_calldata(&planner.0.finalize()).unwrap().actions,
+            vec![Actions::MINT_POSITION_FROM_DELTAS(
+                MintPositionFromDeltasParams {
+                    poolKey: USDC_DAI.pool_key.clone(),
+                    tickLower: I24::unchecked_from(-100),
+                    tickUpper: I24::unchecked_from(100),
+                    amount0Max: 1_000_000,
+                    amount1Max: 2_000_000,
+                    owner,
+                    hookData: Bytes::from_static(&[0x01]),
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn inner_planner_finalizes_to_same_bytes() {
+        let mut planner = V4PositionPlanner::default();
+        planner.add_settle_pair(&USDC.clone(), &DAI.clone());
+        planner.add_sweep(&USDC.clone(), MSG_SENDER);
+        assert_eq!(planner.as_planner(), &planner.0);
+
+        let expected = planner.as_planner().clone().finalize();
+        assert_eq!(V4Planner::from(planner.clone()).finalize(), expected);
+        let inner: V4Planner = planner.into();
+        assert_eq!(inner.finalize(), expected);
+    }
+
+    #[test]
+    fn add_compound_collects_then_increases() {
+        let token_id = U256::from(1);
+        let mut planner = V4PositionPlanner::default();
+        planner
+            .add_compound(
+                token_id,
+                &USDC_DAI,
+                -100,
+                100,
+                1_000_000,
+                2_000_000,
+                Bytes::default(),
+            )
+            .unwrap();
+        let actions = parse_calldata(&planner.0.finalize()).unwrap().actions;
+        assert_eq!(actions.len(), 4);
+        assert_eq!(
+            actions[0],
+            Actions::DECREASE_LIQUIDITY(DecreaseLiquidityParams {
+                tokenId: token_id,
+                liquidity: U256::ZERO,
+                amount0Min: 0,
+                amount1Min: 0,
+                hookData: Bytes::default(),
+            })
+        );
+        let Actions::INCREASE_LIQUIDITY(increase) = &actions[1] else {
+            panic!("expected INCREASE_LIQUIDITY");
+        };
+        let expected_liquidity = max_liquidity_for_amounts(
+            USDC_DAI.sqrt_price_x96,
+            get_sqrt_ratio_at_tick(I24::unchecked_from(-100)).unwrap(),
+            get_sqrt_ratio_at_tick(I24::unchecked_from(100)).unwrap(),
+            U256::from(1_000_000),
+            U256::from(2_000_000),
+            true,
+        );
+        assert_eq!(increase.tokenId, token_id);
+        assert_eq!(
+            increase.liquidity,
+            U256::from(expected_liquidity.to_u128().unwrap())
+        );
+        assert!(increase.liquidity > U256::ZERO);
+        assert_eq!(increase.amount0Max, 1_000_000);
+        assert_eq!(increase.amount1Max, 2_000_000);
+        assert_eq!(
+            actions[2..],
+            [
+                Actions::CLOSE_CURRENCY(CloseCurrencyParams {
+                    currency: DAI.address,
+                }),
+                Actions::CLOSE_CURRENCY(CloseCurrencyParams {
+                    currency: USDC.address,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn add_compound_errors_on_liquidity_overflow() {
+        let mut planner = V4PositionPlanner::default();
+        let result = planner.add_compound(
+            U256::from(1),
+            &USDC_DAI,
+            -10,
+            10,
+            u128::MAX,
+            u128::MAX,
+            Bytes::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::LiquidityOverflow { max_liquidity, .. })
+                if max_liquidity == max_liquidity_per_tick(10)
+        ));
+        assert!(planner.0.actions.is_empty());
+    }
+}